@@ -1,14 +1,17 @@
 //! Storj DCS Access Grant and bound types.
 
-use crate::{helpers, EncryptionKey, Ensurer, Error, Result};
+use crate::{helpers, EncryptionKey, Ensurer, Error, Project, Result};
 
 use std::ffi::{CStr, CString};
 use std::os::raw::c_char;
-use std::time::Duration;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use std::vec::Vec;
 
 use uplink_sys as ulksys;
 
+pub mod inspect;
+pub use inspect::Inspect;
+
 /// Represents an access grant
 ///
 /// An access grant contains everything to access a project and specific
@@ -102,13 +105,13 @@ impl Grant {
         }
 
         Error::new_uplink(uerr).map_or(Ok(()), |err| {
-            drop_uplink_sys_error(uerr);
+            helpers::drop_uplink_sys_error(uerr);
             Err(err)
         })
     }
 
     /// Returns the satellite node URL associated with this access grant.
-    pub fn satellite_address(&self) -> Result<&str> {
+    pub fn satellite_address(&self) -> Result<String> {
         let strres;
         // SAFETY: we trust that the underlying c-binding is safe, nonetheless
         // we ensure strres is correct through the ensure method of the
@@ -117,25 +120,12 @@ impl Grant {
             strres = *ulksys::uplink_access_satellite_address(self.inner.access).ensure();
         }
 
-        if let Some(e) = Error::new_uplink(strres.error) {
-            drop_uplink_sys_string_result(strres);
-            return Err(e);
-        }
-
-        let addrres;
-        // SAFETY: at this point we have already checked that strres.string is
-        // NOT NULL.
-        unsafe {
-            addrres = CStr::from_ptr(strres.string).to_str();
-            drop_uplink_sys_string_result(strres);
-        }
-
-        Ok(addrres.expect("invalid underlying c-binding"))
+        string_from_uplink_c_result(strres, "satellite address")
     }
 
     /// Serializes an access grant such that it can be used to create a
     /// [`Self::new()`] instance of this type or parsed with other tools.
-    pub fn serialize(&self) -> Result<&str> {
+    pub fn serialize(&self) -> Result<String> {
         let strres;
         // SAFETY: we trust that the underlying c-binding is safe, nonetheless
         // we ensure strres is correct through the ensure method of the
@@ -144,20 +134,7 @@ impl Grant {
             strres = *ulksys::uplink_access_serialize(self.inner.access).ensure();
         }
 
-        if let Some(e) = Error::new_uplink(strres.error) {
-            drop_uplink_sys_string_result(strres);
-            return Err(e);
-        }
-
-        let serialized;
-        // SAFETY: at this point we have already checked that strres.string is
-        // NOT NULL.
-        unsafe {
-            serialized = CStr::from_ptr(strres.string).to_str();
-            drop_uplink_sys_string_result(strres);
-        }
-
-        Ok(serialized.expect("invalid underlying c-binding"))
+        string_from_uplink_c_result(strres, "serialized access grant")
     }
 
     /// Creates a new access grant with specific permissions.
@@ -170,13 +147,9 @@ impl Grant {
     /// to only contain enough information to allow access to just those
     /// prefixes.
     ///
-    /// To revoke an access grant see [`Project.revoke_access()`](../project/struct.Project.html#method.revoke_access).
+    /// To revoke an access grant see [`Project::revoke_access()`](crate::Project::revoke_access).
     pub fn share(&self, permission: &Permission, prefixes: Vec<SharePrefix>) -> Result<Grant> {
-        let mut ulk_prefixes: Vec<ulksys::UplinkSharePrefix> = Vec::with_capacity(prefixes.len());
-
-        for sp in prefixes {
-            ulk_prefixes.push(sp.as_uplink_c())
-        }
+        let mut ulk_prefixes = share_prefixes_to_uplink_c(&prefixes);
 
         let accres;
         // SAFETY: we trust that the underlying c-binding is safe, nonetheless
@@ -197,6 +170,80 @@ impl Grant {
             Err(err)
         })
     }
+
+    /// Creates a new access grant with the specific permission and prefixes, as
+    /// [`Grant::share()`], which expires after `ttl`, returning it with its
+    /// expiration time.
+    ///
+    /// The time boundaries of `permission` are intersected with the current
+    /// time plus `ttl` as the not after time and, when `skew` is passed, the
+    /// current time minus `skew` as the not before time, for tolerating the
+    /// clock skew between this machine and the satellite. Both are rounded to
+    /// whole seconds widening the period. The returned expiration time is the
+    /// not after time of the intersection, so it's the one of `permission`
+    /// when it's earlier.
+    ///
+    /// It returns an error if `ttl` is zero or too big to be added to the
+    /// current time, if `skew` makes the not before time the Unix Epoch time
+    /// or before it, or if the time boundaries of `permission` don't overlap
+    /// with the ones set by `ttl` and `skew`.
+    pub fn share_for(
+        &self,
+        permission: &Permission,
+        ttl: Duration,
+        skew: Option<Duration>,
+        prefixes: Vec<SharePrefix>,
+    ) -> Result<(Grant, SystemTime)> {
+        let permission = permission_for(permission, SystemTime::now(), ttl, skew)?;
+        let expires = permission
+            .not_after()
+            .expect("permission_for always sets not after");
+
+        Ok((self.share(&permission, prefixes)?, expires))
+    }
+
+    /// Returns the permissions of the caveats added to this access grant by
+    /// the successive [`Grant::share()`] calls that produced it, in the same
+    /// order.
+    ///
+    /// This access grant is only allowed to do what all of them allow.
+    /// See [`Inspect::permissions()`].
+    pub fn permissions(&self) -> Result<Vec<Permission>> {
+        self.inspect()?.permissions()
+    }
+
+    /// Returns the prefixes to which the successive [`Grant::share()`] calls
+    /// that produced this access grant restricted it, in the same order,
+    /// including the ones which can only be returned encrypted.
+    /// It's empty when this access grant isn't restricted to any prefix.
+    ///
+    /// See [`Inspect::prefixes()`].
+    pub fn prefixes(&self) -> Result<Vec<inspect::CaveatPrefix>> {
+        self.inspect()?.prefixes()
+    }
+
+    /// Returns the effective permission of this access grant, which is the
+    /// intersection of the permissions returned by [`Grant::permissions()`].
+    /// It returns None when their time boundaries don't overlap, hence this
+    /// access grant is never valid.
+    ///
+    /// Sharing this access grant with a permission whose intersection with
+    /// the returned one is None or [`is_empty()`](Permission::is_empty)
+    /// produces a useless access grant.
+    pub fn effective_permission(&self) -> Result<Option<Permission>> {
+        Ok(Permission::effective(&self.permissions()?))
+    }
+
+    /// Decodes this access grant.
+    pub fn inspect(&self) -> Result<Inspect> {
+        Inspect::new(&self.serialize()?)
+    }
+
+    /// Returns the underlying c-bindings access that this access grant wraps.
+    /// The returned pointer is valid as long as `self` is.
+    pub(crate) fn as_uplink_c(&self) -> *mut ulksys::UplinkAccess {
+        self.inner.access
+    }
 }
 
 impl Drop for Grant {
@@ -205,39 +252,168 @@ impl Drop for Grant {
     }
 }
 
-/// Represents a prefix to be shared.
+/// Defines the configuration for opening projects and requesting access
+/// grants.
 #[derive(Debug)]
-pub struct SharePrefix<'a> {
-    bucket: &'a str,
+pub struct Config<'a> {
+    user_agent: &'a str,
+    c_user_agent: CString,
+    dial_timeout: Duration,
+    temp_directory: Option<&'a str>,
+    c_temp_directory: Option<CString>,
+}
+
+impl<'a> Config<'a> {
+    /// Creates a new configuration.
+    ///
+    /// `user_agent` is the value that is sent to the satellites for
+    /// attribution, `dial_timeout` is how long to wait for establishing a
+    /// connection and `temp_directory` is where to save data during downloads
+    /// to use less memory; when it's None the OS temporary directory is used.
+    ///
+    /// It returns an error if `user_agent` or `temp_directory` contains a null
+    /// character (0 byte) or if `dial_timeout` in milliseconds doesn't fit in
+    /// an `i32`.
+    pub fn new(
+        user_agent: &'a str,
+        dial_timeout: Duration,
+        temp_directory: Option<&'a str>,
+    ) -> Result<Self> {
+        let c_user_agent = helpers::cstring_from_str_fn_arg("user_agent", user_agent)?;
+        let c_temp_directory = temp_directory
+            .map(|td| helpers::cstring_from_str_fn_arg("temp_directory", td))
+            .transpose()?;
+
+        if dial_timeout.as_millis() > i32::MAX as u128 {
+            return Err(Error::new_invalid_arguments(
+                "dial_timeout",
+                &format!("milliseconds must be less or equal than {}", i32::MAX),
+            ));
+        }
+
+        Ok(Config {
+            user_agent,
+            c_user_agent,
+            dial_timeout,
+            temp_directory,
+            c_temp_directory,
+        })
+    }
+
+    /// Returns the user agent sent to the satellites.
+    pub fn user_agent(&self) -> &str {
+        self.user_agent
+    }
+
+    /// Returns how long to wait for establishing a connection.
+    pub fn dial_timeout(&self) -> Duration {
+        self.dial_timeout
+    }
+
+    /// Returns the directory where to save data during downloads or None if
+    /// the OS temporary directory is used.
+    pub fn temp_directory(&self) -> Option<&str> {
+        self.temp_directory
+    }
+
+    /// Opens a project with the specific access grant using this
+    /// configuration.
+    pub fn open_project(&self, grant: &Grant) -> Result<Project> {
+        Project::open_with_config(self, grant)
+    }
+
+    /// Generates a new access grant using a passphrase requesting to the
+    /// satellite a project-based salt for deterministic key derivation and
+    /// using this configuration.
+    pub fn request_access_with_passphrase(
+        &self,
+        satellite_addr: &str,
+        api_key: &str,
+        passphrase: &str,
+    ) -> Result<Grant> {
+        let satellite_addr = helpers::cstring_from_str_fn_arg("satellite_addr", satellite_addr)?;
+        let api_key = helpers::cstring_from_str_fn_arg("api_key", api_key)?;
+        let passphrase = helpers::cstring_from_str_fn_arg("passphrase", passphrase)?;
+
+        let accres;
+        // SAFETY: we trust that the underlying c-binding is safe, nonetheless
+        // we ensure accres is correct through the ensure method of the
+        // implemented Ensurer trait.
+        unsafe {
+            accres = *ulksys::uplink_config_request_access_with_passphrase(
+                self.as_uplink_c(),
+                satellite_addr.as_ptr() as *mut c_char,
+                api_key.as_ptr() as *mut c_char,
+                passphrase.as_ptr() as *mut c_char,
+            )
+            .ensure();
+        }
+
+        Error::new_uplink(accres.error).map_or(Ok(Grant { inner: accres }), |err| {
+            drop_uplink_sys_access_result(accres);
+            Err(err)
+        })
+    }
+
+    /// Returns an UplinkConfig with the values of this Config for
+    /// interoperating with the uplink c-bindings.
+    /// The pointer fields of the returned struct will be valid as long as
+    /// `self` is.
+    pub(crate) fn as_uplink_c(&self) -> ulksys::UplinkConfig {
+        ulksys::UplinkConfig {
+            user_agent: self.c_user_agent.as_ptr(),
+            // The conversion is safe because the constructor checks that the
+            // milliseconds fit in an i32.
+            dial_timeout_milliseconds: self.dial_timeout.as_millis() as i32,
+            temp_directory: self
+                .c_temp_directory
+                .as_ref()
+                .map_or(std::ptr::null(), |td| td.as_ptr()),
+        }
+    }
+}
+
+/// Represents a prefix to be shared.
+///
+/// With the `serde` feature, it's serialized as its bucket and prefix and the
+/// deserialization fails if any of them contains a null character (0 byte).
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(into = "SharePrefixFields", try_from = "SharePrefixFields")
+)]
+pub struct SharePrefix {
+    bucket: String,
     c_bucket: CString,
-    prefix: &'a str,
+    prefix: String,
     c_prefix: CString,
 }
 
-impl<'a> SharePrefix<'a> {
+impl SharePrefix {
     /// Create a new prefix to be shared in the specified bucket.
     /// It returns an error if bucket or prefix contains a null character
     /// (0 byte).
-    pub fn new(bucket: &'a str, prefix: &'a str) -> Result<Self> {
+    pub fn new(bucket: &str, prefix: &str) -> Result<Self> {
         let c_bucket = helpers::cstring_from_str_fn_arg("bucket", bucket)?;
         let c_prefix = helpers::cstring_from_str_fn_arg("prefix", prefix)?;
 
         Ok(SharePrefix {
-            bucket,
+            bucket: String::from(bucket),
             c_bucket,
-            prefix,
+            prefix: String::from(prefix),
             c_prefix,
         })
     }
 
     /// Returns the bucket where the prefix to be shared belongs.
     pub fn bucket(&self) -> &str {
-        self.bucket
+        &self.bucket
     }
 
     /// Returns the actual prefix to be shared.
     pub fn prefix(&self) -> &str {
-        self.prefix
+        &self.prefix
     }
 
     /// Returns an UplinkSharePrefix with the values of this SharedPrefix for
@@ -257,9 +433,19 @@ impl<'a> SharePrefix<'a> {
 /// A shared access grant can never has more permission that its parent, hence
 /// even some allowed permission is set for the shared access Grant but not to
 /// its parent, the shared access Grant won't be allowed.
-/// shared access Grant wont
 /// See [`Grant.share()`](struct.Grant.html#method.share).
-#[derive(Default)]
+///
+/// With the `serde` feature, the not before and not after times are serialized
+/// as the number of seconds since the Unix Epoch time, as the underlying
+/// c-bindings represent them, or null when they aren't set. The
+/// deserialization fails when they are invalid, as
+/// [`Permission::set_not_before()`] and [`Permission::set_not_after()`] do.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(into = "PermissionFields", try_from = "PermissionFields")
+)]
 pub struct Permission {
     /// Gives permission to download the content of the objects and their
     /// associated metadata, but it does not allow listing buckets.
@@ -278,16 +464,12 @@ pub struct Permission {
     /// then it must always be before not_after and the resulting access grant
     /// will not work if the satellite believes the time is before the set it
     /// one.
-    /// The time is measured with the number of seconds since the Unix Epoch
-    /// time.
-    not_before: Option<Duration>,
+    not_before: Option<SystemTime>,
     /// Restricts when the resulting access grant is valid for. If it is set
     /// then it must always be after not_before and the resulting access grant
     /// will not work if the satellite believes the time is after the set it
     /// one.
-    /// The time is measured with the number of seconds since the Unix Epoch
-    /// time.
-    not_after: Option<Duration>,
+    not_after: Option<SystemTime>,
 }
 
 impl Permission {
@@ -342,10 +524,9 @@ impl Permission {
         }
     }
 
-    /// Returns the duration from Unix Epoch time since this permission is
-    /// valid.
+    /// Returns the time since this permission is valid.
     /// Return None when there is not before restriction.
-    pub fn not_before(&self) -> Option<Duration> {
+    pub fn not_before(&self) -> Option<SystemTime> {
         self.not_before
     }
 
@@ -353,10 +534,14 @@ impl Permission {
     /// None is passed.
     /// An error is returned if since is more recent or equal to the current
     /// not after valid time of the permission, when not after is set.
-    /// The time is measured with the number of seconds since the Unix Epoch
-    /// time.
-    pub fn set_not_before(&mut self, since: Option<Duration>) -> Result<()> {
+    /// An error is also returned if since isn't after the Unix Epoch time or
+    /// it has a fraction of a second, because the underlying c-bindings
+    /// represent the times with the number of seconds since the Unix Epoch
+    /// time and 0 means that it isn't set.
+    pub fn set_not_before(&mut self, since: Option<SystemTime>) -> Result<()> {
         if let Some(since) = since {
+            helpers::check_unix_secs_time("since", since)?;
+
             if let Some(until) = self.not_after {
                 if since >= until {
                     return Err(
@@ -372,10 +557,9 @@ impl Permission {
         Ok(())
     }
 
-    /// Returns the duration from Unix Epoch time until this permission is
-    /// valid.
+    /// Returns the time until this permission is valid.
     /// Return None when there is not after restriction.
-    pub fn not_after(&self) -> Option<Duration> {
+    pub fn not_after(&self) -> Option<SystemTime> {
         self.not_after
     }
 
@@ -383,10 +567,13 @@ impl Permission {
     /// is passed.
     /// An error is returned if until is previous or equal to the current
     /// not before valid time of the permission, when not before is set.
-    /// The time is measured with the number of seconds since the Unix Epoch
-    /// time.
-    pub fn set_not_after(&mut self, until: Option<Duration>) -> Result<()> {
+    /// An error is also returned if until isn't after the Unix Epoch time or
+    /// it has a fraction of a second, for the same reasons than
+    /// [`Permission::set_not_before()`].
+    pub fn set_not_after(&mut self, until: Option<SystemTime>) -> Result<()> {
         if let Some(until) = until {
+            helpers::check_unix_secs_time("until", until)?;
+
             if let Some(since) = self.not_before {
                 if until <= since {
                     return Err(
@@ -402,6 +589,97 @@ impl Permission {
         Ok(())
     }
 
+    /// Returns the permission that only allows what both, this permission and
+    /// `other`, allow, which is what a shared access grant is allowed to do
+    /// when its parent has one of them and it's shared with the other.
+    ///
+    /// It returns None when the time boundaries of both don't overlap, that is,
+    /// when the resulting not before time would be more recent or equal to the
+    /// resulting not after time, which the setters don't allow.
+    pub fn intersect(&self, other: &Permission) -> Option<Permission> {
+        let not_before = match (self.not_before, other.not_before) {
+            (Some(a), Some(b)) => Some(a.max(b)),
+            (a, b) => a.or(b),
+        };
+        let not_after = match (self.not_after, other.not_after) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        };
+
+        if let (Some(nb), Some(na)) = (not_before, not_after) {
+            if nb >= na {
+                return None;
+            }
+        }
+
+        Some(Permission {
+            allow_download: self.allow_download && other.allow_download,
+            allow_upload: self.allow_upload && other.allow_upload,
+            allow_list: self.allow_list && other.allow_list,
+            allow_delete: self.allow_delete && other.allow_delete,
+            not_before,
+            not_after,
+        })
+    }
+
+    /// Returns the effective permission of an access grant produced by a chain
+    /// of [`Grant::share()`] calls with the permissions of `chain`, which is
+    /// the intersection of all of them. It returns [`Permission::full()`] when
+    /// `chain` is empty and None when their time boundaries don't overlap; see
+    /// [`Permission::intersect()`].
+    ///
+    /// The permissions of the chain of an existing access grant are returned
+    /// by [`Grant::permissions()`].
+    pub fn effective<'a>(chain: impl IntoIterator<Item = &'a Permission>) -> Option<Permission> {
+        chain
+            .into_iter()
+            .try_fold(Permission::full(), |acc, p| acc.intersect(p))
+    }
+
+    /// Returns true if this permission doesn't allow anything that `other`
+    /// doesn't allow, considering the allowed operations and the time
+    /// boundaries.
+    pub fn is_subset_of(&self, other: &Permission) -> bool {
+        let ops = (!self.allow_download || other.allow_download)
+            && (!self.allow_upload || other.allow_upload)
+            && (!self.allow_list || other.allow_list)
+            && (!self.allow_delete || other.allow_delete);
+        let not_before = match (self.not_before, other.not_before) {
+            (_, None) => true,
+            (None, Some(_)) => false,
+            (Some(a), Some(b)) => a >= b,
+        };
+        let not_after = match (self.not_after, other.not_after) {
+            (_, None) => true,
+            (None, Some(_)) => false,
+            (Some(a), Some(b)) => a <= b,
+        };
+
+        self.is_empty() || (ops && not_before && not_after)
+    }
+
+    /// Returns true if `time` is inside of the time boundaries of this
+    /// permission, boundaries included, regardless of the allowed operations.
+    pub fn contains_time(&self, time: SystemTime) -> bool {
+        self.not_before.is_none_or(|nb| time >= nb) && self.not_after.is_none_or(|na| time <= na)
+    }
+
+    /// Returns true if this permission doesn't allow anything, either because
+    /// it doesn't allow any operation or because its not before time is more
+    /// recent or equal to its not after time, as the setters consider it.
+    ///
+    /// An access grant shared with an empty permission is useless.
+    pub fn is_empty(&self) -> bool {
+        let no_ops =
+            !(self.allow_download || self.allow_upload || self.allow_list || self.allow_delete);
+        let no_time = match (self.not_before, self.not_after) {
+            (Some(nb), Some(na)) => nb >= na,
+            _ => false,
+        };
+
+        no_ops || no_time
+    }
+
     /// Returns an UplinkPermission with the values of this Permission for
     /// interoperating with the uplink c-bindings.
     fn to_uplink_c(&self) -> ulksys::UplinkPermission {
@@ -410,12 +688,232 @@ impl Permission {
             allow_upload: self.allow_upload,
             allow_list: self.allow_list,
             allow_delete: self.allow_delete,
-            not_before: self.not_before.map_or(0, |d| d.as_secs()) as i64,
-            not_after: self.not_after.map_or(0, |d| d.as_secs()) as i64,
+            not_before: self
+                .not_before
+                .and_then(helpers::unix_secs_from_system_time)
+                .unwrap_or(0),
+            not_after: self
+                .not_after
+                .and_then(helpers::unix_secs_from_system_time)
+                .unwrap_or(0),
+        }
+    }
+}
+
+/// Getters and setters of the time boundaries with the [`chrono`] types.
+#[cfg(feature = "chrono")]
+impl Permission {
+    /// Same as [`Permission::not_before()`] but with a chrono date time.
+    pub fn not_before_chrono(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        self.not_before.map(chrono::DateTime::from)
+    }
+
+    /// Same as [`Permission::not_after()`] but with a chrono date time.
+    pub fn not_after_chrono(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        self.not_after.map(chrono::DateTime::from)
+    }
+
+    /// Same as [`Permission::set_not_before()`] but with a chrono date time.
+    pub fn set_not_before_chrono<Tz: chrono::TimeZone>(
+        &mut self,
+        since: Option<chrono::DateTime<Tz>>,
+    ) -> Result<()> {
+        self.set_not_before(since.map(SystemTime::from))
+    }
+
+    /// Same as [`Permission::set_not_after()`] but with a chrono date time.
+    pub fn set_not_after_chrono<Tz: chrono::TimeZone>(
+        &mut self,
+        until: Option<chrono::DateTime<Tz>>,
+    ) -> Result<()> {
+        self.set_not_after(until.map(SystemTime::from))
+    }
+}
+
+/// Getters and setters of the time boundaries with the [`time`](::time) types.
+#[cfg(feature = "time")]
+impl Permission {
+    /// Same as [`Permission::not_before()`] but with a time offset date time
+    /// in UTC.
+    pub fn not_before_time(&self) -> Option<::time::OffsetDateTime> {
+        self.not_before.map(::time::OffsetDateTime::from)
+    }
+
+    /// Same as [`Permission::not_after()`] but with a time offset date time in
+    /// UTC.
+    pub fn not_after_time(&self) -> Option<::time::OffsetDateTime> {
+        self.not_after.map(::time::OffsetDateTime::from)
+    }
+
+    /// Same as [`Permission::set_not_before()`] but with a time offset date
+    /// time.
+    pub fn set_not_before_time(&mut self, since: Option<::time::OffsetDateTime>) -> Result<()> {
+        self.set_not_before(since.map(SystemTime::from))
+    }
+
+    /// Same as [`Permission::set_not_after()`] but with a time offset date
+    /// time.
+    pub fn set_not_after_time(&mut self, until: Option<::time::OffsetDateTime>) -> Result<()> {
+        self.set_not_after(until.map(SystemTime::from))
+    }
+}
+
+/// Returns the intersection of `permission` with the time boundaries that
+/// [`Grant::share_for()`] sets when it's called at `now`.
+fn permission_for(
+    permission: &Permission,
+    now: SystemTime,
+    ttl: Duration,
+    skew: Option<Duration>,
+) -> Result<Permission> {
+    if ttl.is_zero() {
+        return Err(Error::new_invalid_arguments("ttl", "cannot be zero"));
+    }
+
+    let since_epoch = now.duration_since(UNIX_EPOCH).map_err(|err| {
+        Error::new_internal_with_inner("system time is before the Unix Epoch time", err.into())
+    })?;
+    let round_down = |d: Duration| UNIX_EPOCH.checked_add(Duration::from_secs(d.as_secs()));
+    let round_up = |d: Duration| {
+        d.checked_add(Duration::from_nanos(999_999_999))
+            .and_then(round_down)
+    };
+
+    let not_after = since_epoch
+        .checked_add(ttl)
+        .and_then(round_up)
+        .ok_or_else(|| {
+            Error::new_invalid_arguments("ttl", "cannot be added to the current time")
+        })?;
+
+    let mut window = Permission::full();
+    window.set_not_after(Some(not_after))?;
+    if let Some(skew) = skew {
+        let not_before = since_epoch
+            .checked_sub(skew)
+            .filter(|d| d.as_secs() > 0)
+            .and_then(round_down)
+            .ok_or_else(|| {
+                Error::new_invalid_arguments(
+                    "skew",
+                    "cannot make the not before time the Unix Epoch time or before it",
+                )
+            })?;
+        window.set_not_before(Some(not_before))?;
+    }
+
+    permission.intersect(&window).ok_or_else(|| {
+        Error::new_invalid_arguments(
+            "permission",
+            "its time boundaries don't overlap with the ones set by ttl and skew",
+        )
+    })
+}
+
+/// The fields of a [`SharePrefix`] for serializing and deserializing it with
+/// serde.
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SharePrefixFields {
+    bucket: String,
+    prefix: String,
+}
+
+#[cfg(feature = "serde")]
+impl From<SharePrefix> for SharePrefixFields {
+    fn from(share_prefix: SharePrefix) -> Self {
+        SharePrefixFields {
+            bucket: share_prefix.bucket,
+            prefix: share_prefix.prefix,
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<SharePrefixFields> for SharePrefix {
+    type Error = Error;
+
+    fn try_from(fields: SharePrefixFields) -> Result<Self> {
+        SharePrefix::new(&fields.bucket, &fields.prefix)
+    }
+}
+
+/// The fields of a [`Permission`] for serializing and deserializing it with
+/// serde. The times are the number of seconds since the Unix Epoch time.
+/// The missing operations aren't allowed and the missing times aren't set.
+#[cfg(feature = "serde")]
+#[derive(Default, serde::Serialize, serde::Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct PermissionFields {
+    allow_download: bool,
+    allow_upload: bool,
+    allow_list: bool,
+    allow_delete: bool,
+    not_before: Option<i64>,
+    not_after: Option<i64>,
+}
+
+#[cfg(feature = "serde")]
+impl From<Permission> for PermissionFields {
+    fn from(perm: Permission) -> Self {
+        // The setters ensure that the times can be represented with the
+        // number of seconds since the Unix Epoch time.
+        PermissionFields {
+            allow_download: perm.allow_download,
+            allow_upload: perm.allow_upload,
+            allow_list: perm.allow_list,
+            allow_delete: perm.allow_delete,
+            not_before: perm
+                .not_before
+                .and_then(helpers::unix_secs_from_system_time),
+            not_after: perm.not_after.and_then(helpers::unix_secs_from_system_time),
         }
     }
 }
 
+#[cfg(feature = "serde")]
+impl TryFrom<PermissionFields> for Permission {
+    type Error = Error;
+
+    fn try_from(fields: PermissionFields) -> Result<Self> {
+        let mut perm = Permission {
+            allow_download: fields.allow_download,
+            allow_upload: fields.allow_upload,
+            allow_list: fields.allow_list,
+            allow_delete: fields.allow_delete,
+            not_before: None,
+            not_after: None,
+        };
+
+        let time = |name, secs: Option<i64>| match secs {
+            None => Ok(None),
+            Some(secs) => helpers::system_time_from_unix_secs(secs)
+                .map(Some)
+                .ok_or_else(|| {
+                    Error::new_invalid_arguments(
+                        name,
+                        "cannot be the Unix Epoch time or before it, or out of range",
+                    )
+                }),
+        };
+        // The setters name the errors after their arguments, which aren't the
+        // names of the serialized fields.
+        let rename = |name| {
+            move |err| match err {
+                Error::InvalidArguments(args) => Error::new_invalid_arguments(name, &args.msg),
+                err => err,
+            }
+        };
+
+        perm.set_not_before(time("not_before", fields.not_before)?)
+            .map_err(rename("not_before"))?;
+        perm.set_not_after(time("not_after", fields.not_after)?)
+            .map_err(rename("not_after"))?;
+        Ok(perm)
+    }
+}
+
 impl Ensurer for ulksys::UplinkAccessResult {
     fn ensure(&self) -> &Self {
         assert!(!self.access.is_null() || !self.error.is_null(), "invalid underlying c-binding returned an invalid UplinkAccessResult; access and error fields are both NULL");
@@ -446,20 +944,46 @@ fn drop_uplink_sys_access_result(accres: ulksys::UplinkAccessResult) {
     }
 }
 
-/// Calls, only if `error` is not null,  the associated `free` underlying
-/// c-bindings function for releasing the associated resources with `error` and
-/// to free the memory pointed by it.
-fn drop_uplink_sys_error(error: *mut ulksys::UplinkError) {
-    if !error.is_null() {
-        // SAFETY: We just checked that the pointer is not null and we trust
-        // that the underlying c-binding is safe freeing its associated
-        // resources and itself.
-        unsafe {
-            ulksys::uplink_free_error(error);
-        }
+/// Returns a copy of the string of an ensured string result returned by the
+/// uplink c-bindings or an error if the result contains one. `what` describes
+/// the string for the error returned when it contains invalid UTF-8
+/// characters.
+///
+/// The resources associated with the passed result are freed by this
+/// function, hence the caller should not use them after this call.
+fn string_from_uplink_c_result(strres: ulksys::UplinkStringResult, what: &str) -> Result<String> {
+    let res = Error::new_uplink(strres.error)
+        .map_or_else(|| string_from_ensured_uplink_c(&strres, what), Err);
+    drop_uplink_sys_string_result(strres);
+    res
+}
+
+/// Returns a copy of the string of an ensured string result without error of
+/// the uplink c-bindings.
+fn string_from_ensured_uplink_c(strres: &ulksys::UplinkStringResult, what: &str) -> Result<String> {
+    // SAFETY: the caller ensures that string isn't NULL and the underlying
+    // c-binding returns NULL terminated strings.
+    match unsafe { CStr::from_ptr(strres.string) }.to_str() {
+        Ok(s) => Ok(String::from(s)),
+        Err(err) => Err(Error::new_internal_with_inner(
+            &format!(
+                "invalid {} because it contains invalid UTF-8 characters",
+                what
+            ),
+            err.into(),
+        )),
     }
 }
 
+/// Returns the UplinkSharePrefix of each of `prefixes` for interoperating with
+/// the uplink c-bindings.
+/// The pointer fields of the returned structs point to the strings owned by
+/// `prefixes`, so they are only valid as long as `prefixes` is alive; taking
+/// them by value would drop them before the c-bindings read them.
+fn share_prefixes_to_uplink_c(prefixes: &[SharePrefix]) -> Vec<ulksys::UplinkSharePrefix> {
+    prefixes.iter().map(SharePrefix::as_uplink_c).collect()
+}
+
 /// Calls the associated `free` underlying c-bindings function for releasing
 /// the associated resources of a string result.
 fn drop_uplink_sys_string_result(strres: ulksys::UplinkStringResult) {
@@ -471,9 +995,30 @@ fn drop_uplink_sys_string_result(strres: ulksys::UplinkStringResult) {
 }
 
 #[cfg(test)]
-mod test {
+pub(crate) mod test {
     use super::*;
+    use crate::encryption_key::test::fake_encryption_key;
     use crate::error;
+    use crate::helpers::test::fake_uplink_c_wrapper;
+
+    use std::mem::ManuallyDrop;
+
+    /// A serialized access grant of the us1.storj.io satellite with a random
+    /// API key and default encryption key, which the underlying c-bindings
+    /// parse without connecting to the satellite.
+    pub(crate) const SERIALIZED_ACCESS: &str = "1Qk3qk584rjLLi1qb9oBCHRW87hbofHSVfvRS2YouRAYT7soq1qCr13YPZDGjA2GRc6vpQEjhiFZxoktHAw96YjLMzPVk8VPCZemik8jFntuYxicfVYzV8hH2TTySFsx1CB4mhLGaDmV1X21TTXao2sCZKEnk7Xxke7kF5EuF26xVAQuj874x8mdpShw1TVZeqwibKoPJhPeB5dHrRpSi2M9gzxYzgPmLtS5szLePAefyHDLWQaowHLt2Tttaoyd4Me5JbQA";
+
+    /// Returns a Grant which isn't dropped and it doesn't hold a valid access,
+    /// so it can only be used for testing the functions that fail before
+    /// passing it to the underlying c-bindings.
+    pub(crate) fn fake_grant() -> ManuallyDrop<Grant> {
+        fake_uplink_c_wrapper(ulksys::UplinkAccess { _handle: 0 }, |access| Grant {
+            inner: ulksys::UplinkAccessResult {
+                access,
+                error: std::ptr::null_mut(),
+            },
+        })
+    }
 
     #[test]
     fn test_grant_new_invalid_param() {
@@ -543,16 +1088,13 @@ mod test {
 
     #[test]
     fn test_grant_override_encryption_key_invalid_params() {
-        // TODO: uncomment this test when the implementation of the
-        // EncryptionKey exists.
-        /*
-        let grant = Grant::new("serialized");
-        let enc_key = EncryptionKey::new();
+        let grant = fake_grant();
+        let enc_key = fake_encryption_key();
 
         {
             // Invalid bucket.
             if let Error::InvalidArguments(error::Args { names, msg }) = grant
-                .override_encryption_key("\0a-bucket", "prefix", enc_key)
+                .override_encryption_key("\0a-bucket", "prefix", &enc_key)
                 .expect_err("when passing a bucket name with NULL bytes")
             {
                 assert_eq!(names, "bucket", "invalid error argument name");
@@ -568,8 +1110,8 @@ mod test {
         {
             // Invalid prefix.
             if let Error::InvalidArguments(error::Args { names, msg }) = grant
-                .override_encryption_key("a-bucket", "pre\0fix", enc_key)
-                .expect_err("when passing a bucket name with NULL bytes")
+                .override_encryption_key("a-bucket", "pre\0fix", &enc_key)
+                .expect_err("when passing a prefix with NULL bytes")
             {
                 assert_eq!(names, "prefix", "invalid error argument name");
                 assert_eq!(
@@ -580,7 +1122,113 @@ mod test {
                 panic!("expected an invalid argument error");
             }
         }
-        */
+    }
+
+    #[test]
+    fn test_config() {
+        {
+            // Pass valid arguments.
+            let config = Config::new("rust-test", Duration::from_secs(10), Some("/tmp"))
+                .expect("new shouldn't fail when passing valid arguments");
+            assert_eq!(config.user_agent(), "rust-test", "user agent");
+            assert_eq!(
+                config.dial_timeout(),
+                Duration::from_secs(10),
+                "dial timeout"
+            );
+            assert_eq!(config.temp_directory(), Some("/tmp"), "temp directory");
+
+            let c_config = config.as_uplink_c();
+            assert_eq!(
+                c_config.dial_timeout_milliseconds, 10000,
+                "c-binding dial timeout"
+            );
+            assert!(
+                !c_config.temp_directory.is_null(),
+                "c-binding temp directory"
+            );
+        }
+
+        {
+            // Pass no temp directory.
+            let config = Config::new("", Duration::from_millis(5), None)
+                .expect("new shouldn't fail when passing valid arguments");
+            assert_eq!(config.temp_directory(), None, "temp directory");
+            assert!(
+                config.as_uplink_c().temp_directory.is_null(),
+                "c-binding temp directory"
+            );
+        }
+
+        {
+            // Pass an invalid user agent.
+            if let Error::InvalidArguments(error::Args { names, msg }) =
+                Config::new("rust\0test", Duration::from_secs(1), None)
+                    .expect_err("new passing a user agent with NULL bytes")
+            {
+                assert_eq!(names, "user_agent", "invalid error argument name");
+                assert_eq!(
+                    msg, "cannot contains null bytes (0 byte). Null byte found at 4",
+                    "invalid error argument message"
+                );
+            } else {
+                panic!("expected an invalid argument error");
+            }
+        }
+
+        {
+            // Pass an invalid temp directory.
+            if let Error::InvalidArguments(error::Args { names, msg }) =
+                Config::new("rust-test", Duration::from_secs(1), Some("/t\0mp"))
+                    .expect_err("new passing a temp directory with NULL bytes")
+            {
+                assert_eq!(names, "temp_directory", "invalid error argument name");
+                assert_eq!(
+                    msg, "cannot contains null bytes (0 byte). Null byte found at 2",
+                    "invalid error argument message"
+                );
+            } else {
+                panic!("expected an invalid argument error");
+            }
+        }
+
+        {
+            // Pass a dial timeout which doesn't fit in an i32 of milliseconds.
+            if let Error::InvalidArguments(error::Args { names, msg }) = Config::new(
+                "rust-test",
+                Duration::from_millis(i32::MAX as u64 + 1),
+                None,
+            )
+            .expect_err("new passing a too big dial timeout")
+            {
+                assert_eq!(names, "dial_timeout", "invalid error argument name");
+                assert_eq!(
+                    msg, "milliseconds must be less or equal than 2147483647",
+                    "invalid error argument message"
+                );
+            } else {
+                panic!("expected an invalid argument error");
+            }
+        }
+    }
+
+    #[test]
+    fn test_config_request_access_with_passphrase_invalid_params() {
+        let config = Config::new("rust-test", Duration::from_secs(1), None)
+            .expect("new shouldn't fail when passing valid arguments");
+
+        if let Error::InvalidArguments(error::Args { names, msg }) = config
+            .request_access_with_passphrase("localh\0st", "some-key", "pass")
+            .expect_err("when passing an satellite address with NULL bytes")
+        {
+            assert_eq!(names, "satellite_addr", "invalid error argument name");
+            assert_eq!(
+                msg, "cannot contains null bytes (0 byte). Null byte found at 6",
+                "invalid error argument message"
+            );
+        } else {
+            panic!("expected an invalid argument error");
+        }
     }
 
     #[test]
@@ -699,19 +1347,19 @@ mod test {
 
         // set not before and after without violating their constraints.
         {
-            perm.set_not_before(Some(Duration::new(5, 50)))
+            perm.set_not_before(Some(UNIX_EPOCH + Duration::from_secs(5)))
                 .expect("set not before");
             assert_eq!(
                 perm.not_before(),
-                Some(Duration::new(5, 50)),
+                Some(UNIX_EPOCH + Duration::from_secs(5)),
                 "set not before"
             );
 
-            perm.set_not_after(Some(Duration::new(5, 51)))
+            perm.set_not_after(Some(UNIX_EPOCH + Duration::from_secs(6)))
                 .expect("set not after");
             assert_eq!(
                 perm.not_after(),
-                Some(Duration::new(5, 51)),
+                Some(UNIX_EPOCH + Duration::from_secs(6)),
                 "set not after"
             );
         }
@@ -719,7 +1367,7 @@ mod test {
         // set not before violating its constraints.
         {
             if let Error::InvalidArguments(error::Args { names, msg }) = perm
-                .set_not_before(Some(Duration::new(5, 52)))
+                .set_not_before(Some(UNIX_EPOCH + Duration::from_secs(7)))
                 .expect_err("set not before")
             {
                 assert_eq!(names, "since", "invalid error argument name");
@@ -736,7 +1384,7 @@ mod test {
         // set not after violating its constraints.
         {
             if let Error::InvalidArguments(error::Args { names, msg }) = perm
-                .set_not_after(Some(Duration::new(5, 50)))
+                .set_not_after(Some(UNIX_EPOCH + Duration::from_secs(5)))
                 .expect_err("set not after")
             {
                 assert_eq!(names, "until", "invalid error argument name");
@@ -760,6 +1408,568 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_permission_invalid_time_boundaries() {
+        let cases = [
+            (UNIX_EPOCH, "cannot be the Unix Epoch time or before it"),
+            (
+                UNIX_EPOCH - Duration::from_secs(1),
+                "cannot be the Unix Epoch time or before it",
+            ),
+            (
+                UNIX_EPOCH + Duration::from_millis(1_500),
+                "cannot have a fraction of a second",
+            ),
+        ];
+
+        for (time, expected_msg) in cases {
+            let mut perm = Permission::full();
+
+            if let Error::InvalidArguments(error::Args { names, msg }) =
+                perm.set_not_before(Some(time)).expect_err("set not before")
+            {
+                assert_eq!(names, "since", "invalid error argument name");
+                assert_eq!(msg, expected_msg, "invalid error argument message");
+            } else {
+                panic!("expected an invalid argument error");
+            }
+
+            if let Error::InvalidArguments(error::Args { names, msg }) =
+                perm.set_not_after(Some(time)).expect_err("set not after")
+            {
+                assert_eq!(names, "until", "invalid error argument name");
+                assert_eq!(msg, expected_msg, "invalid error argument message");
+            } else {
+                panic!("expected an invalid argument error");
+            }
+
+            assert_eq!(perm, Permission::full(), "unmodified permission");
+        }
+    }
+
+    #[test]
+    fn test_permission_to_uplink_c() {
+        let mut perm = Permission::read_only();
+        perm.set_not_before(Some(UNIX_EPOCH + Duration::from_secs(1_600_000_000)))
+            .expect("set not before");
+
+        let uc_perm = perm.to_uplink_c();
+        assert_eq!(uc_perm.not_before, 1_600_000_000, "not before");
+        assert_eq!(uc_perm.not_after, 0, "not after");
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_permission_chrono_time_boundaries() {
+        use chrono::{DateTime, Utc};
+
+        let mut perm = Permission::full();
+        perm.set_not_before_chrono(Some(
+            DateTime::parse_from_rfc3339("2020-09-13T14:26:40+02:00").unwrap(),
+        ))
+        .expect("set not before");
+        perm.set_not_after_chrono(Some(DateTime::<Utc>::from(
+            UNIX_EPOCH + Duration::from_secs(1_700_000_000),
+        )))
+        .expect("set not after");
+
+        assert_eq!(
+            perm.not_before(),
+            Some(UNIX_EPOCH + Duration::from_secs(1_600_000_000)),
+            "not before"
+        );
+        assert_eq!(
+            perm.not_after(),
+            Some(UNIX_EPOCH + Duration::from_secs(1_700_000_000)),
+            "not after"
+        );
+        assert_eq!(
+            perm.not_before_chrono(),
+            Some("2020-09-13T12:26:40Z".parse::<DateTime<Utc>>().unwrap()),
+            "not before chrono"
+        );
+        assert_eq!(
+            perm.not_after_chrono().map(|t| t.timestamp()),
+            Some(1_700_000_000),
+            "not after chrono"
+        );
+        assert_eq!(
+            Permission::full().not_before_chrono(),
+            None,
+            "unset not before chrono"
+        );
+
+        perm.set_not_before_chrono(Some(
+            DateTime::parse_from_rfc3339("2020-09-13T12:26:40.5Z").unwrap(),
+        ))
+        .expect_err("fraction of a second");
+        perm.set_not_after_chrono(Some(
+            DateTime::parse_from_rfc3339("1969-12-31T23:59:59Z").unwrap(),
+        ))
+        .expect_err("before the Unix Epoch time");
+    }
+
+    #[cfg(feature = "time")]
+    #[test]
+    fn test_permission_time_time_boundaries() {
+        use ::time::OffsetDateTime;
+
+        let mut perm = Permission::full();
+        perm.set_not_before_time(Some(
+            OffsetDateTime::from_unix_timestamp(1_600_000_000).unwrap(),
+        ))
+        .expect("set not before");
+        perm.set_not_after_time(None).expect("set not after");
+
+        assert_eq!(
+            perm.not_before(),
+            Some(UNIX_EPOCH + Duration::from_secs(1_600_000_000)),
+            "not before"
+        );
+        assert_eq!(perm.not_after(), None, "not after");
+        assert_eq!(
+            perm.not_before_time(),
+            Some(OffsetDateTime::from_unix_timestamp(1_600_000_000).unwrap()),
+            "not before time"
+        );
+        assert_eq!(perm.not_after_time(), None, "not after time");
+
+        perm.set_not_after_time(Some(
+            OffsetDateTime::from_unix_timestamp_nanos(1_700_000_000_000_000_001).unwrap(),
+        ))
+        .expect_err("fraction of a second");
+        perm.set_not_after_time(Some(OffsetDateTime::UNIX_EPOCH))
+            .expect_err("Unix Epoch time");
+    }
+
+    #[test]
+    fn test_permission_for() {
+        let now = UNIX_EPOCH + Duration::new(1_600_000_000, 500);
+        let mut parent = Permission::read_only();
+        parent
+            .set_not_after(Some(UNIX_EPOCH + Duration::from_secs(1_900_000_000)))
+            .unwrap();
+
+        let shared =
+            permission_for(&parent, now, Duration::from_secs(3_600), None).expect("without skew");
+        assert_eq!(
+            shared,
+            perm([true, false, true, false], None, Some(1_600_003_601)),
+            "without skew"
+        );
+
+        let shared = permission_for(
+            &parent,
+            now,
+            Duration::from_secs(3_600),
+            Some(Duration::from_millis(1_500)),
+        )
+        .expect("with skew");
+        assert_eq!(
+            shared,
+            perm(
+                [true, false, true, false],
+                Some(1_599_999_998),
+                Some(1_600_003_601)
+            ),
+            "with skew"
+        );
+
+        let shared = permission_for(
+            &parent,
+            UNIX_EPOCH + Duration::from_secs(1_600_000_000),
+            Duration::from_secs(60),
+            Some(Duration::from_secs(30)),
+        )
+        .expect("at a whole second");
+        assert_eq!(
+            shared,
+            perm(
+                [true, false, true, false],
+                Some(1_599_999_970),
+                Some(1_600_000_060)
+            ),
+            "at a whole second"
+        );
+
+        let mut narrow = Permission::full();
+        narrow
+            .set_not_before(Some(UNIX_EPOCH + Duration::from_secs(1_600_000_010)))
+            .unwrap();
+        narrow
+            .set_not_after(Some(UNIX_EPOCH + Duration::from_secs(1_600_000_030)))
+            .unwrap();
+        let shared = permission_for(
+            &narrow,
+            now,
+            Duration::from_secs(3_600),
+            Some(Duration::from_secs(60)),
+        )
+        .expect("narrower time boundaries");
+        assert_eq!(
+            shared,
+            perm([true; 4], Some(1_600_000_010), Some(1_600_000_030)),
+            "narrower time boundaries"
+        );
+
+        if let Error::InvalidArguments(error::Args { names, msg }) =
+            permission_for(&narrow, now, Duration::from_secs(5), None)
+                .expect_err("non overlapping time boundaries")
+        {
+            assert_eq!(names, "permission", "invalid error argument name");
+            assert_eq!(
+                msg, "its time boundaries don't overlap with the ones set by ttl and skew",
+                "invalid error argument message"
+            );
+        } else {
+            panic!("expected an invalid argument error");
+        }
+
+        if let Error::InvalidArguments(error::Args { names, msg }) =
+            permission_for(&parent, now, Duration::ZERO, None).expect_err("zero ttl")
+        {
+            assert_eq!(names, "ttl", "invalid error argument name");
+            assert_eq!(msg, "cannot be zero", "invalid error argument message");
+        } else {
+            panic!("expected an invalid argument error");
+        }
+
+        if let Error::InvalidArguments(error::Args { names, msg }) =
+            permission_for(&parent, now, Duration::MAX, None).expect_err("overflowing ttl")
+        {
+            assert_eq!(names, "ttl", "invalid error argument name");
+            assert_eq!(
+                msg, "cannot be added to the current time",
+                "invalid error argument message"
+            );
+        } else {
+            panic!("expected an invalid argument error");
+        }
+
+        for skew in [
+            Duration::from_secs(1_600_000_000),
+            Duration::new(1_599_999_999, 600),
+            Duration::MAX,
+        ] {
+            if let Error::InvalidArguments(error::Args { names, msg }) =
+                permission_for(&parent, now, Duration::from_secs(60), Some(skew))
+                    .expect_err("too big skew")
+            {
+                assert_eq!(names, "skew", "invalid error argument name");
+                assert_eq!(
+                    msg, "cannot make the not before time the Unix Epoch time or before it",
+                    "invalid error argument message"
+                );
+            } else {
+                panic!("expected an invalid argument error");
+            }
+        }
+    }
+
+    /// Returns a permission with the passed allowed operations and time
+    /// boundaries in seconds since the Unix Epoch time.
+    fn perm(ops: [bool; 4], not_before: Option<u64>, not_after: Option<u64>) -> Permission {
+        Permission {
+            allow_download: ops[0],
+            allow_upload: ops[1],
+            allow_list: ops[2],
+            allow_delete: ops[3],
+            not_before: not_before.map(|s| UNIX_EPOCH + Duration::from_secs(s)),
+            not_after: not_after.map(|s| UNIX_EPOCH + Duration::from_secs(s)),
+        }
+    }
+
+    #[test]
+    fn test_permission_intersect() {
+        let a = perm([true, true, false, true], Some(10), None);
+        let b = perm([true, false, true, true], Some(5), Some(20));
+
+        assert_eq!(
+            a.intersect(&b),
+            Some(perm([true, false, false, true], Some(10), Some(20))),
+            "intersection"
+        );
+        assert_eq!(a.intersect(&b), b.intersect(&a), "commutative");
+        assert_eq!(
+            a.intersect(&Permission::full()),
+            Some(a.clone()),
+            "identity"
+        );
+        assert_eq!(
+            a.intersect(&perm([true; 4], None, Some(9))),
+            None,
+            "non overlapping time boundaries"
+        );
+        assert_eq!(
+            a.intersect(&perm([true; 4], None, Some(10))),
+            None,
+            "single instant time boundaries"
+        );
+    }
+
+    #[test]
+    fn test_permission_effective() {
+        assert_eq!(
+            Permission::effective(&[]),
+            Some(Permission::full()),
+            "empty chain"
+        );
+
+        let chain = [
+            Permission::full(),
+            perm([true, false, true, true], None, Some(30)),
+            perm([true, true, true, false], Some(10), Some(40)),
+        ];
+        assert_eq!(
+            Permission::effective(&chain),
+            Some(perm([true, false, true, false], Some(10), Some(30))),
+            "chain"
+        );
+        assert_eq!(
+            Permission::effective(&[chain[1].clone(), perm([true; 4], Some(30), None)]),
+            None,
+            "non overlapping chain"
+        );
+    }
+
+    #[test]
+    fn test_permission_is_subset_of() {
+        let parent = perm([true, false, true, false], Some(10), Some(30));
+
+        assert!(parent.is_subset_of(&parent), "itself");
+        assert!(parent.is_subset_of(&Permission::full()), "full");
+        assert!(
+            perm([true, false, false, false], Some(15), Some(20)).is_subset_of(&parent),
+            "fewer operations and narrower time boundaries"
+        );
+        assert!(
+            !perm([true, true, false, false], Some(15), Some(20)).is_subset_of(&parent),
+            "more operations"
+        );
+        assert!(
+            !perm([true, false, false, false], None, Some(20)).is_subset_of(&parent),
+            "without not before"
+        );
+        assert!(
+            !perm([true, false, false, false], Some(15), Some(31)).is_subset_of(&parent),
+            "later not after"
+        );
+        assert!(Permission::new().is_subset_of(&parent), "empty permission");
+    }
+
+    #[test]
+    fn test_permission_contains_time() {
+        let p = perm([true; 4], Some(10), Some(20));
+
+        assert!(
+            p.contains_time(UNIX_EPOCH + Duration::from_secs(10)),
+            "not before boundary"
+        );
+        assert!(
+            p.contains_time(UNIX_EPOCH + Duration::from_secs(20)),
+            "not after boundary"
+        );
+        assert!(
+            !p.contains_time(UNIX_EPOCH + Duration::from_secs(9)),
+            "before not before"
+        );
+        assert!(
+            !p.contains_time(UNIX_EPOCH + Duration::from_secs(21)),
+            "after not after"
+        );
+        assert!(
+            !p.contains_time(UNIX_EPOCH - Duration::from_secs(1)),
+            "before the Unix Epoch time"
+        );
+        assert!(
+            Permission::full().contains_time(UNIX_EPOCH - Duration::from_secs(1)),
+            "without time boundaries"
+        );
+    }
+
+    #[test]
+    fn test_permission_is_empty() {
+        assert!(Permission::new().is_empty(), "without operations");
+        assert!(!Permission::full().is_empty(), "full");
+        assert!(
+            perm([true; 4], Some(21), Some(20)).is_empty(),
+            "non overlapping time boundaries"
+        );
+        assert!(
+            perm([true; 4], Some(20), Some(20)).is_empty(),
+            "single instant time boundaries"
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_permission_serde() {
+        let mut perm = Permission::read_only();
+        perm.set_not_before(Some(UNIX_EPOCH + Duration::from_secs(10)))
+            .expect("set not before");
+        perm.set_not_after(Some(UNIX_EPOCH + Duration::from_secs(20)))
+            .expect("set not after");
+
+        let json = serde_json::to_string(&perm).expect("serialize");
+        assert_eq!(
+            json,
+            r#"{"allow_download":true,"allow_upload":false,"allow_list":true,"allow_delete":false,"not_before":10,"not_after":20}"#,
+            "serialized"
+        );
+        assert_eq!(
+            serde_json::from_str::<Permission>(&json).expect("deserialize"),
+            perm,
+            "round trip"
+        );
+        assert_eq!(
+            serde_json::to_string(&Permission::new()).expect("serialize"),
+            r#"{"allow_download":false,"allow_upload":false,"allow_list":false,"allow_delete":false,"not_before":null,"not_after":null}"#,
+            "serialized without times"
+        );
+
+        assert_eq!(
+            serde_json::from_str::<Permission>(r#"{"allow_upload": true}"#)
+                .expect("deserialize with missing fields"),
+            Permission {
+                allow_upload: true,
+                ..Permission::new()
+            },
+            "missing fields"
+        );
+
+        for (json, field) in [
+            (r#"{"not_before": 20, "not_after": 20}"#, "not_after"),
+            (r#"{"not_before": 0}"#, "not_before"),
+            (r#"{"not_before": -1}"#, "not_before"),
+            (r#"{"not_after": 0}"#, "not_after"),
+        ] {
+            let err = serde_json::from_str::<Permission>(json).expect_err("invalid times");
+            assert!(
+                err.to_string()
+                    .starts_with(&format!("{} argurments have invalid values", field)),
+                "invalid time boundaries error of {}: {}",
+                json,
+                err
+            );
+        }
+
+        serde_json::from_str::<Permission>(r#"{"allow_downlaod": true}"#)
+            .expect_err("unknown field");
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_share_prefix_serde() {
+        let sp = SharePrefix::new("a-bucket", "a/prefix/").expect("new share prefix");
+
+        let json = serde_json::to_string(&sp).expect("serialize");
+        assert_eq!(json, r#"{"bucket":"a-bucket","prefix":"a/prefix/"}"#);
+        assert_eq!(
+            serde_json::from_str::<SharePrefix>(&json).expect("deserialize"),
+            sp,
+            "round trip"
+        );
+
+        let err =
+            serde_json::from_str::<SharePrefix>(r#"{"bucket":"a-bucket","prefix":"a/\u0000"}"#)
+                .expect_err("prefix with NULL bytes");
+        assert!(
+            err.to_string().contains("prefix"),
+            "NULL bytes error: {}",
+            err
+        );
+    }
+
+    #[test]
+    fn test_string_from_ensured_uplink_c() {
+        {
+            // The returned string outlives the c-bindings one.
+            let mut c_string = CString::new("127.0.0.1:7777")
+                .unwrap()
+                .into_bytes_with_nul();
+            let strres = ulksys::UplinkStringResult {
+                string: c_string.as_mut_ptr() as *mut c_char,
+                error: std::ptr::null_mut(),
+            };
+
+            let addr =
+                string_from_ensured_uplink_c(&strres, "satellite address").expect("valid string");
+            // Overwrite the memory like freeing and reusing it would do.
+            c_string.iter_mut().for_each(|b| *b = b'x');
+            drop(c_string);
+
+            assert_eq!(addr, "127.0.0.1:7777", "copied string");
+        }
+
+        {
+            // Invalid UTF-8 characters.
+            let c_string = CString::new(vec![0xc3, 0x28]).unwrap();
+            let strres = ulksys::UplinkStringResult {
+                string: c_string.as_ptr() as *mut c_char,
+                error: std::ptr::null_mut(),
+            };
+
+            if let Error::Internal(error::Internal { ctx_msg, .. }) =
+                string_from_ensured_uplink_c(&strres, "serialized access grant")
+                    .expect_err("invalid UTF-8 string")
+            {
+                assert_eq!(
+                    ctx_msg,
+                    "invalid serialized access grant because it contains invalid UTF-8 characters",
+                    "invalid error context message"
+                );
+            } else {
+                panic!("expected an internal error");
+            }
+        }
+    }
+
+    #[test]
+    fn test_grant_share_prefixes() {
+        let grant = Grant::new(SERIALIZED_ACCESS).expect("valid serialized access grant");
+        let prefixes = vec![
+            SharePrefix::new("a-bucket", "a/prefix").unwrap(),
+            SharePrefix::new("other-bucket", "other").unwrap(),
+        ];
+
+        // The c-bindings read the buckets and prefixes of the passed
+        // prefixes, which Grant::share takes by value.
+        let shared = grant
+            .share(&Permission::full(), prefixes.clone())
+            .expect("share with prefixes");
+
+        assert_eq!(
+            shared
+                .prefixes()
+                .expect("prefixes of the shared access grant"),
+            prefixes
+                .into_iter()
+                .map(inspect::CaveatPrefix::Decrypted)
+                .collect::<Vec<_>>(),
+            "shared prefixes"
+        );
+    }
+
+    #[test]
+    fn test_grant_strings_outlive_uplink_c_calls() {
+        let grant = Grant::new(SERIALIZED_ACCESS).expect("valid serialized access grant");
+        let serialized = grant.serialize().expect("serialize access grant");
+        let satellite = grant.satellite_address().expect("satellite address");
+
+        // Further calls to the c-bindings may reuse the memory of the
+        // previously returned and freed strings.
+        let other = Grant::new(SERIALIZED_ACCESS).expect("valid serialized access grant");
+        let other_serialized = other.serialize().expect("serialize access grant");
+        other.satellite_address().expect("satellite address");
+        grant.serialize().expect("serialize access grant");
+
+        assert_eq!(serialized, other_serialized, "serialized access grant");
+        assert_eq!(
+            satellite, "12EayRS2V1kEsWESU9QMRseFhdxYxKicsiFmxrsLZHeLUtdps3S@us1.storj.io:7777",
+            "satellite address"
+        );
+    }
+
     // Test Ensurer implementaitons
     use std::ptr::null_mut;
 
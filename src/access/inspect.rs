@@ -0,0 +1,966 @@
+//! Offline inspection of serialized access grants.
+//!
+//! It decodes a serialized access grant without the underlying c-bindings,
+//! so the grant is never handed to them. The decoding only reveals what the
+//! grant contains; it doesn't verify the API key signature, hence it doesn't
+//! guarantee that the satellite accepts the grant.
+
+use crate::access::{Permission, SharePrefix};
+use crate::{Error, Result};
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// The version byte of the base58check encoding of the access grants.
+const ACCESS_GRANT_VERSION: u8 = 0;
+
+/// The version of the binary format of the API key macaroons.
+const MACAROON_VERSION: u8 = 2;
+
+/// The name of the argument used in the errors returned by the decoding
+/// functions.
+const ARG_NAME: &str = "serialized_access";
+
+/// Contains the decoded information of a serialized access grant.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Inspect {
+    /// The satellite node URL associated with the access grant.
+    pub satellite_address: String,
+    /// The head of the API key, which identifies the API key of the project
+    /// from which the access grant derives.
+    pub api_key_head: Vec<u8>,
+    /// The restrictions of the API key in the order that they were added.
+    /// An access grant is allowed to do an operation only if all of them
+    /// allow it.
+    pub caveats: Vec<Caveat>,
+    /// The encryption information of the access grant.
+    pub encryption: EncryptionAccess,
+}
+
+impl Inspect {
+    /// Decodes a serialized access grant.
+    ///
+    /// It returns an error if `serialized_access` isn't a valid serialized
+    /// access grant.
+    pub fn new(serialized_access: &str) -> Result<Self> {
+        let data = bs58::decode(serialized_access)
+            .with_check(Some(ACCESS_GRANT_VERSION))
+            .into_vec()
+            .map_err(|err| invalid_arg(&format!("invalid base58check encoding: {}", err)))?;
+
+        // The first byte is the version, which is verified by the decoder.
+        Self::from_scope(&data[1..])
+    }
+
+    /// Returns true if there is an instant when all the caveats allow
+    /// accessing to the objects.
+    ///
+    /// An access grant whose caveats' validity periods don't overlap can
+    /// never be used.
+    pub fn is_usable(&self) -> bool {
+        let not_before = self.caveats.iter().filter_map(|c| c.not_before).max();
+        let not_after = self.caveats.iter().filter_map(|c| c.not_after).min();
+
+        match (not_before, not_after) {
+            (Some(nb), Some(na)) => nb <= na,
+            _ => true,
+        }
+    }
+
+    /// Returns the time when the access grant expires, that is, the earliest
+    /// `not_after` of its caveats. None when it never expires.
+    pub fn expires(&self) -> Option<SystemTime> {
+        self.caveats.iter().filter_map(|c| c.not_after).min()
+    }
+
+    /// Returns the permission of each caveat in the same order. An access
+    /// grant is only allowed to do what all of them allow.
+    ///
+    /// It returns an error if the permission of any caveat is invalid; see
+    /// [`Caveat::permission()`].
+    pub fn permissions(&self) -> Result<Vec<Permission>> {
+        self.caveats.iter().map(Caveat::permission).collect()
+    }
+
+    /// Returns the allowed paths of all the caveats in the same order.
+    ///
+    /// The caveats only contain the encrypted prefixes, so they are resolved
+    /// through the encryption store entries into share prefixes. The allowed
+    /// paths which don't have an entry are returned with their encrypted
+    /// prefix; that happens with the paths of the caveats of previous shares
+    /// that a later share narrowed. They still restrict the access grant, for
+    /// example, an access grant shared to a bucket and then to another one
+    /// cannot access any of them.
+    ///
+    /// It returns an error if any resolved bucket or prefix contains a null
+    /// character (0 byte).
+    pub fn prefixes(&self) -> Result<Vec<CaveatPrefix>> {
+        self.caveats
+            .iter()
+            .flat_map(|c| c.allowed_paths.iter())
+            .map(|path| {
+                match self.encryption.store_entries.iter().find(|e| {
+                    e.bucket == path.bucket && e.encrypted_prefix == path.encrypted_prefix
+                }) {
+                    Some(e) => SharePrefix::new(&e.bucket, &e.unencrypted_prefix)
+                        .map(CaveatPrefix::Decrypted),
+                    None => Ok(CaveatPrefix::Encrypted(path.clone())),
+                }
+            })
+            .collect()
+    }
+
+    /// Decodes the scope protobuf message, which is the top level message of
+    /// a serialized access grant.
+    fn from_scope(data: &[u8]) -> Result<Self> {
+        let mut satellite_address = None;
+        let mut api_key = None;
+        let mut encryption = None;
+
+        for field in ProtoReader::new(data, "scope") {
+            match field? {
+                (1, Value::Bytes(b)) => satellite_address = Some(utf8(b, "satellite address")?),
+                (2, Value::Bytes(b)) => api_key = Some(b),
+                (3, Value::Bytes(b)) => encryption = Some(EncryptionAccess::from_proto(b)?),
+                _ => {}
+            }
+        }
+
+        let satellite_address =
+            satellite_address.ok_or_else(|| invalid_arg("missing satellite address"))?;
+        let api_key = api_key.ok_or_else(|| invalid_arg("missing API key"))?;
+        let (api_key_head, caveats) = parse_macaroon(api_key)?;
+        let caveats = caveats
+            .into_iter()
+            .map(Caveat::from_proto)
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Inspect {
+            satellite_address,
+            api_key_head,
+            caveats,
+            encryption: encryption.unwrap_or_default(),
+        })
+    }
+}
+
+/// A restriction of an API key.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Caveat {
+    /// Disallows downloading objects.
+    pub disallow_reads: bool,
+    /// Disallows uploading objects.
+    pub disallow_writes: bool,
+    /// Disallows listing buckets and objects.
+    pub disallow_lists: bool,
+    /// Disallows deleting buckets and objects.
+    pub disallow_deletes: bool,
+    /// Restricts the access to the objects under these paths. Empty for not
+    /// restricting it.
+    pub allowed_paths: Vec<CaveatPath>,
+    /// Time before which the access isn't allowed. None for no restriction.
+    pub not_before: Option<SystemTime>,
+    /// Time after which the access isn't allowed. None for no restriction.
+    pub not_after: Option<SystemTime>,
+}
+
+impl Caveat {
+    /// Decodes a caveat protobuf message.
+    fn from_proto(data: &[u8]) -> Result<Self> {
+        let mut caveat = Caveat::default();
+
+        for field in ProtoReader::new(data, "caveat") {
+            match field? {
+                (1, Value::Varint(v)) => caveat.disallow_reads = v != 0,
+                (2, Value::Varint(v)) => caveat.disallow_writes = v != 0,
+                (3, Value::Varint(v)) => caveat.disallow_lists = v != 0,
+                (4, Value::Varint(v)) => caveat.disallow_deletes = v != 0,
+                (10, Value::Bytes(b)) => caveat.allowed_paths.push(CaveatPath::from_proto(b)?),
+                (20, Value::Bytes(b)) => caveat.not_after = Some(timestamp(b, "not after")?),
+                (21, Value::Bytes(b)) => caveat.not_before = Some(timestamp(b, "not before")?),
+                _ => {}
+            }
+        }
+
+        Ok(caveat)
+    }
+
+    /// Returns the permission that this caveat grants.
+    ///
+    /// The caveat times may have a fraction of a second, which a [`Permission`]
+    /// doesn't allow, so `not_before` is rounded up and `not_after` is rounded
+    /// down to the second, narrowing the time boundaries rather than widening
+    /// them.
+    ///
+    /// It returns the error of [`Permission::set_not_before()`] or
+    /// [`Permission::set_not_after()`] when the rounded times are the Unix
+    /// Epoch time or before it, or `not_before` is more recent or equal to
+    /// `not_after`.
+    pub fn permission(&self) -> Result<Permission> {
+        let mut permission = Permission {
+            allow_download: !self.disallow_reads,
+            allow_upload: !self.disallow_writes,
+            allow_list: !self.disallow_lists,
+            allow_delete: !self.disallow_deletes,
+            ..Permission::default()
+        };
+
+        let round_down = |t: SystemTime| {
+            t.duration_since(UNIX_EPOCH)
+                .map(|d| UNIX_EPOCH + Duration::from_secs(d.as_secs()))
+                .unwrap_or(t)
+        };
+        let round_up = |t: SystemTime| {
+            let rounded = round_down(t);
+            if rounded < t {
+                rounded.checked_add(Duration::from_secs(1)).unwrap_or(t)
+            } else {
+                rounded
+            }
+        };
+
+        permission.set_not_after(self.not_after.map(round_down))?;
+        permission.set_not_before(self.not_before.map(round_up))?;
+        Ok(permission)
+    }
+}
+
+/// A path to which a caveat restricts the access.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CaveatPath {
+    /// The name of the bucket.
+    pub bucket: String,
+    /// The encrypted prefix of the object keys. Empty for the whole bucket.
+    pub encrypted_prefix: Vec<u8>,
+}
+
+/// An allowed path of a caveat; see [`Inspect::prefixes()`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CaveatPrefix {
+    /// The path resolved through an encryption store entry.
+    Decrypted(SharePrefix),
+    /// The path which doesn't have an encryption store entry, so only its
+    /// encrypted prefix is known.
+    Encrypted(CaveatPath),
+}
+
+impl CaveatPath {
+    /// Decodes a caveat path protobuf message.
+    fn from_proto(data: &[u8]) -> Result<Self> {
+        let mut path = CaveatPath::default();
+
+        for field in ProtoReader::new(data, "caveat path") {
+            match field? {
+                (1, Value::Bytes(b)) => path.bucket = utf8(b, "caveat path bucket")?,
+                (2, Value::Bytes(b)) => path.encrypted_prefix = b.to_vec(),
+                _ => {}
+            }
+        }
+
+        Ok(path)
+    }
+}
+
+/// The encryption information of an access grant.
+///
+/// The encryption keys aren't exposed, only if they are present.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EncryptionAccess {
+    /// Indicates if there is a default encryption key, which is used for the
+    /// paths which don't match any entry.
+    pub has_default_key: bool,
+    /// The cipher used for encrypting the paths which don't match any entry.
+    pub default_path_cipher: CipherSuite,
+    /// The encryption information of specific bucket and prefixes.
+    pub store_entries: Vec<StoreEntry>,
+}
+
+impl EncryptionAccess {
+    /// Decodes an encryption access protobuf message.
+    fn from_proto(data: &[u8]) -> Result<Self> {
+        let mut encryption = EncryptionAccess::default();
+
+        for field in ProtoReader::new(data, "encryption access") {
+            match field? {
+                (1, Value::Bytes(b)) => encryption.has_default_key = !b.is_empty(),
+                (2, Value::Bytes(b)) => encryption.store_entries.push(StoreEntry::from_proto(b)?),
+                (3, Value::Varint(v)) => encryption.default_path_cipher = CipherSuite::from(v),
+                _ => {}
+            }
+        }
+
+        Ok(encryption)
+    }
+}
+
+/// The encryption information of a specific bucket and prefix.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StoreEntry {
+    /// The name of the bucket.
+    pub bucket: String,
+    /// The prefix of the object keys.
+    pub unencrypted_prefix: String,
+    /// The encrypted prefix of the object keys.
+    pub encrypted_prefix: Vec<u8>,
+    /// Indicates if there is an encryption key for the prefix.
+    pub has_key: bool,
+    /// The cipher used for encrypting the paths under the prefix.
+    pub path_cipher: CipherSuite,
+}
+
+impl StoreEntry {
+    /// Decodes an encryption access store entry protobuf message.
+    fn from_proto(data: &[u8]) -> Result<Self> {
+        let mut entry = StoreEntry::default();
+
+        for field in ProtoReader::new(data, "encryption store entry") {
+            match field? {
+                (1, Value::Bytes(b)) => entry.bucket = utf8(b, "encryption store entry bucket")?,
+                (2, Value::Bytes(b)) => {
+                    entry.unencrypted_prefix = utf8(b, "encryption store entry prefix")?
+                }
+                (3, Value::Bytes(b)) => entry.encrypted_prefix = b.to_vec(),
+                (4, Value::Bytes(b)) => entry.has_key = !b.is_empty(),
+                (5, Value::Varint(v)) => entry.path_cipher = CipherSuite::from(v),
+                _ => {}
+            }
+        }
+
+        Ok(entry)
+    }
+}
+
+/// The ciphers used for encrypting the object keys.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CipherSuite {
+    /// The cipher isn't specified.
+    #[default]
+    Unspecified,
+    /// The object keys aren't encrypted.
+    Null,
+    /// AES-GCM.
+    AesGcm,
+    /// NaCl secretbox.
+    SecretBox,
+    /// The object keys aren't encrypted but they are base64url encoded.
+    NullBase64Url,
+    /// A cipher unknown by this crate with its numeric identifier.
+    Unknown(u64),
+}
+
+impl From<u64> for CipherSuite {
+    fn from(v: u64) -> Self {
+        match v {
+            0 => CipherSuite::Unspecified,
+            1 => CipherSuite::Null,
+            2 => CipherSuite::AesGcm,
+            3 => CipherSuite::SecretBox,
+            4 => CipherSuite::NullBase64Url,
+            v => CipherSuite::Unknown(v),
+        }
+    }
+}
+
+/// Returns an invalid argument error for the serialized access grant.
+fn invalid_arg(msg: &str) -> Error {
+    Error::new_invalid_arguments(ARG_NAME, msg)
+}
+
+/// Converts the bytes of a field to a String. `what` describes the field for
+/// the returned error when it contains invalid UTF-8 characters.
+fn utf8(data: &[u8], what: &str) -> Result<String> {
+    std::str::from_utf8(data)
+        .map(String::from)
+        .map_err(|_| invalid_arg(&format!("invalid {} UTF-8 encoding", what)))
+}
+
+/// Decodes a `google.protobuf.Timestamp` protobuf message. `what` describes
+/// the field for the returned error when it's invalid.
+fn timestamp(data: &[u8], what: &str) -> Result<SystemTime> {
+    let (mut secs, mut nanos) = (0u64, 0u64);
+    for field in ProtoReader::new(data, "timestamp") {
+        match field? {
+            (1, Value::Varint(v)) => secs = v,
+            (2, Value::Varint(v)) => nanos = v,
+            _ => {}
+        }
+    }
+
+    // Negative values are encoded as their two's complement.
+    if secs > i64::MAX as u64 || nanos >= 1_000_000_000 {
+        return Err(invalid_arg(&format!(
+            "invalid {} time because it's before the Unix Epoch time or malformed",
+            what
+        )));
+    }
+
+    UNIX_EPOCH
+        .checked_add(Duration::new(secs, nanos as u32))
+        .ok_or_else(|| {
+            invalid_arg(&format!(
+                "invalid {} time because it's out of the range of the system time",
+                what
+            ))
+        })
+}
+
+/// Parses an API key, which is serialized as a macaroon in the libmacaroons
+/// v2 binary format, returning its head and its caveats.
+fn parse_macaroon(data: &[u8]) -> Result<(Vec<u8>, Vec<&[u8]>)> {
+    const FIELD_LOCATION: u8 = 1;
+    const FIELD_IDENTIFIER: u8 = 2;
+    const FIELD_SIGNATURE: u8 = 6;
+
+    match data.first() {
+        None => return Err(invalid_arg("empty API key")),
+        Some(&v) if v != MACAROON_VERSION => {
+            return Err(invalid_arg("invalid API key macaroon version"))
+        }
+        _ => {}
+    }
+
+    let mut rest = &data[1..];
+    let mut section = parse_macaroon_section(&mut rest)?;
+    if section.first().map(|p| p.0) == Some(FIELD_LOCATION) {
+        section.remove(0);
+    }
+    let head = match section.as_slice() {
+        [(FIELD_IDENTIFIER, head)] => head.to_vec(),
+        _ => return Err(invalid_arg("invalid API key macaroon header")),
+    };
+
+    let mut caveats = Vec::new();
+    loop {
+        let mut section = parse_macaroon_section(&mut rest)?;
+        if section.is_empty() {
+            break;
+        }
+        if section[0].0 == FIELD_LOCATION {
+            section.remove(0);
+        }
+        match section.first() {
+            Some(&(FIELD_IDENTIFIER, caveat)) => caveats.push(caveat),
+            _ => return Err(invalid_arg("invalid API key macaroon caveat")),
+        }
+    }
+
+    match parse_macaroon_packet(&mut rest)? {
+        (FIELD_SIGNATURE, sig) if sig.len() == 32 => Ok((head, caveats)),
+        _ => Err(invalid_arg("invalid API key macaroon signature")),
+    }
+}
+
+/// Parses the packets of a macaroon section, which ends with an end of section
+/// field, advancing `data` after it.
+fn parse_macaroon_section<'a>(data: &mut &'a [u8]) -> Result<Vec<(u8, &'a [u8])>> {
+    let mut packets: Vec<(u8, &[u8])> = Vec::new();
+    loop {
+        if data.is_empty() {
+            return Err(invalid_arg("API key macaroon section extends past the end"));
+        }
+
+        let packet = parse_macaroon_packet(data)?;
+        if packet.0 == 0 {
+            return Ok(packets);
+        }
+        if packets.last().is_some_and(|p| packet.0 <= p.0) {
+            return Err(invalid_arg("API key macaroon fields out of order"));
+        }
+        packets.push(packet);
+    }
+}
+
+/// Parses a macaroon packet, which is a field type followed by the data length
+/// and the data, advancing `data` after it. The end of section field doesn't
+/// have length nor data.
+fn parse_macaroon_packet<'a>(data: &mut &'a [u8]) -> Result<(u8, &'a [u8])> {
+    let (&field_type, rest) = data
+        .split_first()
+        .ok_or_else(|| invalid_arg("API key macaroon packet extends past the end"))?;
+    *data = rest;
+    if field_type == 0 {
+        return Ok((0, &[]));
+    }
+
+    let len =
+        read_varint(data).ok_or_else(|| invalid_arg("invalid API key macaroon packet length"))?;
+    take(data, len)
+        .map(|d| (field_type, d))
+        .ok_or_else(|| invalid_arg("API key macaroon packet extends past the end"))
+}
+
+/// A value of a protobuf field.
+enum Value<'a> {
+    /// Varint wire type, used for integers, booleans and enums.
+    Varint(u64),
+    /// Length-delimited wire type, used for strings, bytes and messages.
+    Bytes(&'a [u8]),
+    /// Fixed 64 or 32 bits wire types; their value isn't used by any decoded
+    /// message.
+    Fixed,
+}
+
+/// Iterates over the fields of an encoded protobuf message returning their
+/// number and their value.
+struct ProtoReader<'a> {
+    data: &'a [u8],
+    /// The name of the message for the errors.
+    msg_name: &'static str,
+}
+
+impl<'a> ProtoReader<'a> {
+    fn new(data: &'a [u8], msg_name: &'static str) -> Self {
+        ProtoReader { data, msg_name }
+    }
+
+    fn malformed(&mut self) -> Error {
+        // Stop the iteration after an error.
+        self.data = &[];
+        invalid_arg(&format!("malformed {} protobuf message", self.msg_name))
+    }
+}
+
+impl<'a> Iterator for ProtoReader<'a> {
+    type Item = Result<(u64, Value<'a>)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.data.is_empty() {
+            return None;
+        }
+
+        let key = match read_varint(&mut self.data) {
+            Some(k) => k,
+            None => return Some(Err(self.malformed())),
+        };
+
+        let value = match key & 0x7 {
+            0 => read_varint(&mut self.data).map(Value::Varint),
+            1 => take(&mut self.data, 8).map(|_| Value::Fixed),
+            2 => read_varint(&mut self.data)
+                .and_then(|len| take(&mut self.data, len))
+                .map(Value::Bytes),
+            5 => take(&mut self.data, 4).map(|_| Value::Fixed),
+            _ => None,
+        };
+
+        Some(value.map(|v| (key >> 3, v)).ok_or_else(|| self.malformed()))
+    }
+}
+
+/// Reads a base 128 varint from `data` advancing it after the varint. It
+/// returns None if the varint is truncated or it overflows 64 bits.
+fn read_varint(data: &mut &[u8]) -> Option<u64> {
+    let mut value: u64 = 0;
+    for (i, &b) in data.iter().enumerate().take(10) {
+        value |= u64::from(b & 0x7f) << (7 * i);
+        if b & 0x80 == 0 {
+            if i == 9 && b > 1 {
+                return None;
+            }
+            *data = &data[i + 1..];
+            return Some(value);
+        }
+    }
+
+    None
+}
+
+/// Takes `len` bytes from `data` advancing it after them. It returns None if
+/// `data` has less than `len` bytes.
+fn take<'a>(data: &mut &'a [u8], len: u64) -> Option<&'a [u8]> {
+    let len = usize::try_from(len).ok()?;
+    if data.len() < len {
+        return None;
+    }
+
+    let (taken, rest) = data.split_at(len);
+    *data = rest;
+    Some(taken)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::error;
+
+    /// Encodes protobuf messages for building serialized access grants in the
+    /// tests.
+    #[derive(Default)]
+    struct ProtoWriter(Vec<u8>);
+
+    impl ProtoWriter {
+        fn varint(&mut self, mut v: u64) {
+            while v >= 0x80 {
+                self.0.push((v as u8) | 0x80);
+                v >>= 7;
+            }
+            self.0.push(v as u8);
+        }
+
+        fn uint(mut self, field: u64, v: u64) -> Self {
+            self.varint(field << 3);
+            self.varint(v);
+            self
+        }
+
+        fn bytes(mut self, field: u64, b: &[u8]) -> Self {
+            self.varint((field << 3) | 2);
+            self.varint(b.len() as u64);
+            self.0.extend_from_slice(b);
+            self
+        }
+    }
+
+    /// Returns a serialized macaroon with the passed head and caveats.
+    fn macaroon(head: &[u8], caveats: &[Vec<u8>]) -> Vec<u8> {
+        fn packet(data: &mut Vec<u8>, field_type: u8, b: &[u8]) {
+            data.push(field_type);
+            let mut w = ProtoWriter::default();
+            w.varint(b.len() as u64);
+            data.extend(w.0);
+            data.extend_from_slice(b);
+        }
+
+        let mut data = vec![MACAROON_VERSION];
+        packet(&mut data, 2, head);
+        data.push(0);
+        for c in caveats {
+            packet(&mut data, 2, c);
+            data.push(0);
+        }
+        data.push(0);
+        packet(&mut data, 6, &[7; 32]);
+        data
+    }
+
+    /// Returns a caveat protobuf message.
+    fn caveat(
+        disallow: [bool; 4],
+        paths: &[(&str, &[u8])],
+        not_before: Option<u64>,
+        not_after: Option<u64>,
+    ) -> Vec<u8> {
+        let mut w = ProtoWriter::default();
+        for (i, d) in disallow.iter().enumerate() {
+            if *d {
+                w = w.uint(i as u64 + 1, 1);
+            }
+        }
+        for (bucket, prefix) in paths {
+            let p = ProtoWriter::default()
+                .bytes(1, bucket.as_bytes())
+                .bytes(2, prefix);
+            w = w.bytes(10, &p.0);
+        }
+        if let Some(na) = not_after {
+            w = w.bytes(20, &ProtoWriter::default().uint(1, na).0);
+        }
+        if let Some(nb) = not_before {
+            w = w.bytes(21, &ProtoWriter::default().uint(1, nb).0);
+        }
+        w.bytes(30, b"nonce").0
+    }
+
+    /// Returns a serialized access grant with the passed API key.
+    fn serialized_access(api_key: &[u8]) -> String {
+        let entry = ProtoWriter::default()
+            .bytes(1, b"a-bucket")
+            .bytes(2, b"tenant/")
+            .bytes(3, b"encrypted")
+            .bytes(4, &[1; 32])
+            .uint(5, 2);
+        let encryption = ProtoWriter::default()
+            .bytes(1, &[2; 32])
+            .bytes(2, &entry.0)
+            .uint(3, 2)
+            // EncryptionParameters, which aren't decoded.
+            .bytes(4, &ProtoWriter::default().uint(1, 2).0);
+        let scope = ProtoWriter::default()
+            .bytes(1, b"sat@127.0.0.1:7777")
+            .bytes(2, api_key)
+            .bytes(3, &encryption.0);
+
+        bs58::encode(scope.0)
+            .with_check_version(ACCESS_GRANT_VERSION)
+            .into_string()
+    }
+
+    #[test]
+    fn test_inspect() {
+        let api_key = macaroon(
+            b"head",
+            &[
+                caveat([false, true, false, true], &[], None, Some(1_700_000_000)),
+                caveat(
+                    [false, true, false, true],
+                    &[("a-bucket", b"enc-prefix")],
+                    Some(1_600_000_000),
+                    None,
+                ),
+            ],
+        );
+        let inspect = Inspect::new(&serialized_access(&api_key)).expect("valid access grant");
+
+        assert_eq!(
+            inspect.satellite_address, "sat@127.0.0.1:7777",
+            "satellite address"
+        );
+        assert_eq!(inspect.api_key_head, b"head", "API key head");
+        assert_eq!(
+            inspect.caveats,
+            vec![
+                Caveat {
+                    disallow_reads: false,
+                    disallow_writes: true,
+                    disallow_lists: false,
+                    disallow_deletes: true,
+                    allowed_paths: vec![],
+                    not_before: None,
+                    not_after: Some(UNIX_EPOCH + Duration::from_secs(1_700_000_000)),
+                },
+                Caveat {
+                    disallow_reads: false,
+                    disallow_writes: true,
+                    disallow_lists: false,
+                    disallow_deletes: true,
+                    allowed_paths: vec![CaveatPath {
+                        bucket: String::from("a-bucket"),
+                        encrypted_prefix: b"enc-prefix".to_vec(),
+                    }],
+                    not_before: Some(UNIX_EPOCH + Duration::from_secs(1_600_000_000)),
+                    not_after: None,
+                },
+            ],
+            "caveats"
+        );
+        assert_eq!(
+            inspect.encryption,
+            EncryptionAccess {
+                has_default_key: true,
+                default_path_cipher: CipherSuite::AesGcm,
+                store_entries: vec![StoreEntry {
+                    bucket: String::from("a-bucket"),
+                    unencrypted_prefix: String::from("tenant/"),
+                    encrypted_prefix: b"encrypted".to_vec(),
+                    has_key: true,
+                    path_cipher: CipherSuite::AesGcm,
+                }],
+            },
+            "encryption"
+        );
+        assert_eq!(
+            inspect.expires(),
+            Some(UNIX_EPOCH + Duration::from_secs(1_700_000_000)),
+            "expires"
+        );
+        assert!(inspect.is_usable(), "is usable");
+    }
+
+    #[test]
+    fn test_inspect_permissions_and_prefixes() {
+        let api_key = macaroon(
+            b"head",
+            &[
+                caveat([false; 4], &[], None, None),
+                caveat(
+                    [false, true, false, true],
+                    &[("a-bucket", b"encrypted"), ("a-bucket", b"unknown")],
+                    Some(1_600_000_000),
+                    Some(1_700_000_000),
+                ),
+            ],
+        );
+        let inspect = Inspect::new(&serialized_access(&api_key)).expect("valid access grant");
+
+        let mut read_list = Permission::read_only();
+        read_list
+            .set_not_before(Some(UNIX_EPOCH + Duration::from_secs(1_600_000_000)))
+            .unwrap();
+        read_list
+            .set_not_after(Some(UNIX_EPOCH + Duration::from_secs(1_700_000_000)))
+            .unwrap();
+        assert_eq!(
+            inspect.permissions().expect("valid permissions"),
+            vec![Permission::full(), read_list],
+            "permissions"
+        );
+
+        assert_eq!(
+            inspect.prefixes().expect("valid prefixes"),
+            vec![
+                CaveatPrefix::Decrypted(SharePrefix::new("a-bucket", "tenant/").unwrap()),
+                CaveatPrefix::Encrypted(CaveatPath {
+                    bucket: String::from("a-bucket"),
+                    encrypted_prefix: b"unknown".to_vec(),
+                }),
+            ],
+            "prefixes"
+        );
+    }
+
+    #[test]
+    fn test_caveat_permission() {
+        let secs = |s: u64, nanos: u32| Some(UNIX_EPOCH + Duration::new(s, nanos));
+
+        {
+            // Times with a fraction of a second narrow the time boundaries.
+            let caveat = Caveat {
+                disallow_writes: true,
+                not_before: secs(1_600_000_000, 1),
+                not_after: secs(1_700_000_000, 999_999_999),
+                ..Caveat::default()
+            };
+
+            let mut expected = Permission::full();
+            expected.allow_upload = false;
+            expected.set_not_before(secs(1_600_000_001, 0)).unwrap();
+            expected.set_not_after(secs(1_700_000_000, 0)).unwrap();
+            assert_eq!(
+                caveat.permission().expect("valid permission"),
+                expected,
+                "permission"
+            );
+        }
+
+        {
+            // Time boundaries which are empty once rounded.
+            let caveat = Caveat {
+                not_before: secs(1_600_000_000, 1),
+                not_after: secs(1_600_000_000, 2),
+                ..Caveat::default()
+            };
+
+            if let Error::InvalidArguments(error::Args { names, .. }) = caveat
+                .permission()
+                .expect_err("not before more recent than not after")
+            {
+                assert_eq!(names, "since", "invalid error argument name");
+            } else {
+                panic!("expected an invalid argument error");
+            }
+        }
+
+        {
+            // Not before at the Unix Epoch time.
+            let caveat = Caveat {
+                not_before: secs(0, 0),
+                ..Caveat::default()
+            };
+
+            if let Error::InvalidArguments(error::Args { names, msg }) = caveat
+                .permission()
+                .expect_err("not before at the Unix Epoch")
+            {
+                assert_eq!(names, "since", "invalid error argument name");
+                assert_eq!(
+                    msg, "cannot be the Unix Epoch time or before it",
+                    "invalid error argument message"
+                );
+            } else {
+                panic!("expected an invalid argument error");
+            }
+        }
+    }
+
+    #[test]
+    fn test_inspect_not_usable() {
+        let api_key = macaroon(
+            b"head",
+            &[
+                caveat([false; 4], &[], None, Some(1_600_000_000)),
+                caveat([false; 4], &[], Some(1_700_000_000), None),
+            ],
+        );
+        let inspect = Inspect::new(&serialized_access(&api_key)).expect("valid access grant");
+        assert!(!inspect.is_usable(), "is usable");
+    }
+
+    #[test]
+    fn test_inspect_invalid() {
+        let assert_invalid = |serialized: &str, expected_msg: &str| {
+            if let Error::InvalidArguments(error::Args { names, msg }) =
+                Inspect::new(serialized).expect_err("invalid access grant")
+            {
+                assert_eq!(names, "serialized_access", "invalid error argument name");
+                assert!(
+                    msg.starts_with(expected_msg),
+                    "invalid error argument message: {}",
+                    msg
+                );
+            } else {
+                panic!("expected an invalid argument error");
+            }
+        };
+
+        assert_invalid("not-base58-0OIl", "invalid base58check encoding");
+
+        let mut corrupted = serialized_access(&macaroon(b"head", &[]));
+        let last = if corrupted.ends_with('2') { '3' } else { '2' };
+        corrupted.pop();
+        corrupted.push(last);
+        assert_invalid(&corrupted, "invalid base58check encoding");
+
+        assert_invalid(
+            &bs58::encode([0, 0xff])
+                .with_check_version(ACCESS_GRANT_VERSION)
+                .into_string(),
+            "malformed scope protobuf message",
+        );
+
+        let mut api_key = macaroon(b"head", &[]);
+        api_key[0] = 1;
+        assert_invalid(
+            &serialized_access(&api_key),
+            "invalid API key macaroon version",
+        );
+
+        let mut api_key = macaroon(b"head", &[]);
+        api_key.pop();
+        assert_invalid(
+            &serialized_access(&api_key),
+            "API key macaroon packet extends past the end",
+        );
+
+        assert_invalid(
+            &serialized_access(&macaroon(b"head", &[vec![0x08]])),
+            "malformed caveat protobuf message",
+        );
+    }
+
+    #[test]
+    fn test_timestamp() {
+        let proto = |secs: u64, nanos: u64| ProtoWriter::default().uint(1, secs).uint(2, nanos).0;
+
+        assert_eq!(
+            timestamp(&proto(1_600_000_000, 500), "not after").expect("valid timestamp"),
+            UNIX_EPOCH + Duration::new(1_600_000_000, 500),
+            "valid timestamp"
+        );
+        timestamp(&proto(u64::MAX, 0), "not after").expect_err("negative seconds");
+        timestamp(&proto(1, 1_000_000_000), "not after").expect_err("invalid nanoseconds");
+        // It's an error, rather than a panic, where the system time cannot
+        // represent it.
+        let _ = timestamp(&proto(i64::MAX as u64, 999_999_999), "not after");
+    }
+
+    #[test]
+    fn test_read_varint() {
+        let cases: [(&[u8], Option<u64>); 5] = [
+            (&[0x00], Some(0)),
+            (&[0x96, 0x01], Some(150)),
+            (
+                &[0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x01],
+                Some(u64::MAX),
+            ),
+            (
+                &[0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x02],
+                None,
+            ),
+            (&[0x96], None),
+        ];
+
+        for (data, expected) in cases {
+            let mut d = data;
+            assert_eq!(read_varint(&mut d), expected, "varint {:?}", data);
+        }
+    }
+}
@@ -1,72 +1,70 @@
 //! Storj DSC Bucket and related types.
 
-use crate::{Ensurer, Error, Result};
+use crate::{helpers, Ensurer, Error, Result};
 
-use std::ffi::CStr;
-use std::time::Duration;
+use std::ffi::{CStr, CString};
+use std::time::SystemTime;
 
 use uplink_sys as ulksys;
 
 /// Contains information about a specific bucket.
-pub struct Bucket<'a> {
-    /// The bucket type of the underlying c-bindings Rust crate that an instance
-    /// of this struct represents and guard its life time until this instance
-    /// drops.
-    inner: *mut ulksys::UplinkBucket,
-
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bucket {
     /// Name of the bucket.
-    pub name: &'a str,
-    /// Unix Epoch time when the bucket was created.
-    pub created_at: Duration,
+    pub name: String,
+    /// Time when the bucket was created.
+    pub created_at: SystemTime,
 }
 
-impl<'a> Bucket<'a> {
+impl Bucket {
     /// Creates a Bucket instance from the type exposed by the uplink
     /// c-bindings.
     ///
-    /// The returned Bucket owns the address of the passed pointer, hence the
-    /// caller should not use that pointer after this call nor free it because
-    /// the returned Bucket will free it when it is dropped.
+    /// The returned Bucket has a copy of the values of the passed pointer and
+    /// this function frees it, hence the caller should not use that pointer
+    /// after this call nor free it, even when an error is returned, unless
+    /// it's null.
     pub(crate) fn from_uplink_c(uc_bucket: *mut ulksys::UplinkBucket) -> Result<Self> {
         if uc_bucket.is_null() {
             return Err(Error::new_invalid_arguments("uc_bucket", "cannot be null"));
         }
 
-        let name: &str;
-        let created_at: Duration;
+        let bucket;
         // SAFETY: uc_bucket cannot be null because it's checked at the
         // beginning of the function and we ensure uc_bucket doesn't have fields
         // with NULL pointes through the ensure method of the implemented
-        // Ensurer trait.
+        // Ensurer trait. uc_bucket isn't used after it's freed.
         unsafe {
-            (*uc_bucket).ensure();
-
-            match CStr::from_ptr((*uc_bucket).name).to_str() {
-                Ok(n) => name = n,
-                Err(err) => {
-                    return Err(Error::new_internal_with_inner(
-                        "invalid bucket name because it contains invalid UTF-8 characters",
-                        err.into(),
-                    ));
-                }
-            };
-
-            created_at = Duration::new((*uc_bucket).created as u64, 0);
+            bucket = Self::from_ensured_uplink_c((*uc_bucket).ensure());
+            ulksys::uplink_free_bucket(uc_bucket);
         }
 
-        Ok(Bucket {
-            inner: uc_bucket,
-            name,
-            created_at,
-        })
+        bucket
     }
-}
 
-impl<'a> Drop for Bucket<'a> {
-    fn drop(&mut self) {
-        // SAFETY: we trust that the underlying c-binding is safe freeing the
-        // memory of a correct UplinkBucket value.
-        unsafe { ulksys::uplink_free_bucket(self.inner) }
+    /// Creates a Bucket instance copying the values of an ensured bucket of the
+    /// uplink c-bindings.
+    fn from_ensured_uplink_c(uc_bucket: &ulksys::UplinkBucket) -> Result<Self> {
+        // SAFETY: the caller ensures that name isn't NULL and the underlying
+        // c-binding returns NULL terminated strings.
+        let name = match unsafe { CStr::from_ptr(uc_bucket.name) }.to_str() {
+            Ok(n) => String::from(n),
+            Err(err) => {
+                return Err(Error::new_internal_with_inner(
+                    "invalid bucket name because it contains invalid UTF-8 characters",
+                    err.into(),
+                ));
+            }
+        };
+
+        let created_at =
+            helpers::system_time_from_unix_secs(uc_bucket.created).ok_or_else(|| {
+                Error::new_internal(
+                    "invalid bucket creation time because it's before the Unix Epoch time or out of range",
+                )
+            })?;
+
+        Ok(Bucket { name, created_at })
     }
 }
 
@@ -76,6 +74,8 @@ pub struct Iterator {
     /// an instance of this struct represents and guard its life time until this
     /// instance drops.
     inner: *mut ulksys::UplinkBucketIterator,
+    /// The name of the last bucket returned by the iterator.
+    cursor: Option<String>,
 }
 
 impl Iterator {
@@ -89,23 +89,44 @@ impl Iterator {
             ));
         }
 
-        Ok(Iterator { inner: uc_iterator })
+        Ok(Iterator {
+            inner: uc_iterator,
+            cursor: None,
+        })
+    }
+
+    /// Returns the name of the last bucket returned by this iterator or None
+    /// if it hasn't returned any yet.
+    ///
+    /// The returned value can be used as the cursor of [`ListBucketsOptions`]
+    /// for listing the buckets after it, for example, for resuming the listing
+    /// in another process.
+    pub fn cursor(&self) -> Option<&str> {
+        self.cursor.as_deref()
     }
 }
 
-impl<'a> std::iter::Iterator for &'a Iterator {
-    type Item = Result<Bucket<'a>>;
+impl std::iter::Iterator for Iterator {
+    type Item = Result<Bucket>;
 
     fn next(&mut self) -> Option<Self::Item> {
+        // SAFETY: we trust that the underlying c-binding is safe and
+        // self.inner is never NULL because it's checked by the constructor.
         unsafe {
             if !ulksys::uplink_bucket_iterator_next(self.inner) {
                 let uc_error = ulksys::uplink_bucket_iterator_err(self.inner);
-                return Error::new_uplink(uc_error).map(Err);
+                return Error::new_uplink(uc_error).map(|err| {
+                    helpers::drop_uplink_sys_error(uc_error);
+                    Err(err)
+                });
             }
 
-            Some(Bucket::from_uplink_c(ulksys::uplink_bucket_iterator_item(
-                self.inner,
-            )))
+            let bucket = Bucket::from_uplink_c(ulksys::uplink_bucket_iterator_item(self.inner));
+            if let Ok(b) = &bucket {
+                self.cursor = Some(b.name.clone());
+            }
+
+            Some(bucket)
         }
     }
 }
@@ -120,6 +141,50 @@ impl Drop for Iterator {
     }
 }
 
+/// Options for listing buckets.
+#[derive(Debug)]
+pub struct ListBucketsOptions<'a> {
+    cursor: &'a str,
+    c_cursor: CString,
+}
+
+impl<'a> ListBucketsOptions<'a> {
+    /// Creates options for listing buckets starting after the bucket named
+    /// `cursor`; the first listed bucket is the one after the cursor.
+    /// An empty cursor lists from the beginning.
+    /// It returns an error if cursor contains a null character (0 byte).
+    pub fn new(cursor: &'a str) -> Result<Self> {
+        let c_cursor = helpers::cstring_from_str_fn_arg("cursor", cursor)?;
+
+        Ok(ListBucketsOptions { cursor, c_cursor })
+    }
+
+    /// Returns the name of the bucket after which the listing starts.
+    pub fn cursor(&self) -> &str {
+        self.cursor
+    }
+
+    /// Returns an UplinkListBucketsOptions with the values of this
+    /// ListBucketsOptions for interoperating with the uplink c-bindings.
+    /// The pointer fields of the returned struct will be valid as long as
+    /// `self` is.
+    pub(crate) fn as_uplink_c(&self) -> ulksys::UplinkListBucketsOptions {
+        ulksys::UplinkListBucketsOptions {
+            cursor: self.c_cursor.as_ptr(),
+        }
+    }
+}
+
+impl Ensurer for ulksys::UplinkBucketResult {
+    fn ensure(&self) -> &Self {
+        assert!(!self.bucket.is_null() || !self.error.is_null(), "invalid underlying c-binding returned an invalid UplinkBucketResult; bucket and error fields are both NULL");
+        assert!((self.bucket.is_null() && !self.error.is_null())
+            || (!self.bucket.is_null() && self.error.is_null()),
+            "invalid underlying c-binding returned an invalid UplinkBucketResult; bucket and error fields are both NOT NULL");
+        self
+    }
+}
+
 impl Ensurer for ulksys::UplinkBucket {
     fn ensure(&self) -> &Self {
         assert!(
@@ -129,3 +194,173 @@ impl Ensurer for ulksys::UplinkBucket {
         self
     }
 }
+
+/// Calls the associated `free` underlying c-bindings function for releasing
+/// the associated resources of a bucket result.
+pub(crate) fn drop_uplink_sys_bucket_result(bucket_res: ulksys::UplinkBucketResult) {
+    // SAFETY: we trust that the underlying c-binding is safe freeing the
+    // memory of a correct UplinkBucketResult value.
+    unsafe {
+        ulksys::uplink_free_bucket_result(bucket_res);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::error;
+
+    use std::ptr::null_mut;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    #[test]
+    fn test_bucket_from_ensured_uplink_c() {
+        {
+            // Valid bucket.
+            let name = CString::new("a-bucket").unwrap();
+            let bucket = Bucket::from_ensured_uplink_c(&ulksys::UplinkBucket {
+                name: name.as_ptr() as *mut _,
+                created: 1_600_000_000,
+            })
+            .expect("valid bucket");
+
+            assert_eq!(bucket.name, "a-bucket", "name");
+            assert_eq!(
+                bucket.created_at,
+                UNIX_EPOCH + Duration::from_secs(1_600_000_000),
+                "created at"
+            );
+        }
+
+        {
+            // Creation time before the Unix Epoch time.
+            let name = CString::new("a-bucket").unwrap();
+            if let Error::Internal(error::Internal { ctx_msg, .. }) =
+                Bucket::from_ensured_uplink_c(&ulksys::UplinkBucket {
+                    name: name.as_ptr() as *mut _,
+                    created: -1,
+                })
+                .expect_err("creation time before Unix Epoch time")
+            {
+                assert_eq!(
+                    ctx_msg, "invalid bucket creation time because it's before the Unix Epoch time or out of range",
+                    "invalid error context message"
+                );
+            } else {
+                panic!("expected an internal error");
+            }
+        }
+    }
+
+    #[test]
+    fn test_bucket_is_send_sync_clone() {
+        fn assert_send_sync_clone<T: Send + Sync + Clone>() {}
+        assert_send_sync_clone::<Bucket>();
+    }
+
+    #[test]
+    fn test_list_buckets_options() {
+        {
+            // Pass a valid cursor.
+            let opts = ListBucketsOptions::new("a-bucket")
+                .expect("new shouldn't fail when passing a valid cursor");
+            assert_eq!(opts.cursor(), "a-bucket", "cursor");
+
+            let c_opts = opts.as_uplink_c();
+            // SAFETY: the pointer is valid as long as opts is.
+            let c_cursor = unsafe { CStr::from_ptr(c_opts.cursor) };
+            assert_eq!(c_cursor.to_str().unwrap(), "a-bucket", "c-binding cursor");
+        }
+
+        {
+            // Pass an invalid cursor.
+            if let Error::InvalidArguments(error::Args { names, msg }) =
+                ListBucketsOptions::new("a-\0bucket")
+                    .expect_err("new passing a cursor with NULL bytes")
+            {
+                assert_eq!(names, "cursor", "invalid error argument name");
+                assert_eq!(
+                    msg, "cannot contains null bytes (0 byte). Null byte found at 2",
+                    "invalid error argument message"
+                );
+            } else {
+                panic!("expected an invalid argument error");
+            }
+        }
+    }
+
+    #[test]
+    fn test_ensurer_ulksys_bucket_result_valid() {
+        {
+            // Has a bucket
+            let bucket_res = ulksys::UplinkBucketResult {
+                bucket: &mut ulksys::UplinkBucket {
+                    name: CString::new("a-bucket").unwrap().into_raw(),
+                    created: 0,
+                },
+                error: null_mut::<ulksys::UplinkError>(),
+            };
+
+            bucket_res.ensure();
+        }
+
+        {
+            // Has an error
+            let bucket_res = ulksys::UplinkBucketResult {
+                bucket: null_mut::<ulksys::UplinkBucket>(),
+                error: &mut ulksys::UplinkError {
+                    code: 0,
+                    message: null_mut(),
+                },
+            };
+
+            bucket_res.ensure();
+        }
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "invalid underlying c-binding returned an invalid UplinkBucketResult; bucket and error fields are both NULL"
+    )]
+    fn test_ensurer_ulksys_bucket_result_invalid_both_null() {
+        let bucket_res = ulksys::UplinkBucketResult {
+            bucket: null_mut::<ulksys::UplinkBucket>(),
+            error: null_mut::<ulksys::UplinkError>(),
+        };
+
+        bucket_res.ensure();
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "invalid underlying c-binding returned an invalid UplinkBucketResult; bucket and error fields are both NOT NULL"
+    )]
+    fn test_ensurer_ulksys_bucket_result_invalid_both_not_null() {
+        let bucket_res = ulksys::UplinkBucketResult {
+            bucket: &mut ulksys::UplinkBucket {
+                name: CString::new("a-bucket").unwrap().into_raw(),
+                created: 0,
+            },
+            error: &mut ulksys::UplinkError {
+                code: 0,
+                message: null_mut(),
+            },
+        };
+
+        bucket_res.ensure();
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "invalid underlying c-binding returned invalid UplinkBucket; name field is NULL"
+    )]
+    fn test_ensurer_ulksys_bucket_invalid_name_null() {
+        let bucket = ulksys::UplinkBucket {
+            name: null_mut(),
+            created: 0,
+        };
+
+        bucket.ensure();
+    }
+}
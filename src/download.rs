@@ -0,0 +1,320 @@
+//! Storj DCS Download and related types.
+
+use crate::object::Object;
+use crate::{helpers, Ensurer, Error, Result};
+
+use std::io;
+use std::os::raw::c_void;
+
+use uplink_sys as ulksys;
+
+/// The error code that the underlying c-bindings returns when there isn't more
+/// data to read; it's the value of the `EOF` C macro.
+const UPLINK_EOF_CODE: i32 = -1;
+
+/// Downloads an object.
+///
+/// The content of the object is read through the implementation of the
+/// [`std::io::Read`] trait.
+#[derive(Debug)]
+pub struct Download {
+    /// The download type of the underlying c-bindings Rust crate that an
+    /// instance of this struct represents and guard its life time until this
+    /// instance drops.
+    /// It's a download result because it's the one that holds the download and
+    /// allows to free its memory.
+    inner: ulksys::UplinkDownloadResult,
+}
+
+impl Download {
+    /// Creates a Download instance from an ensured download result returned by
+    /// the uplink c-bindings or an error if the result contains one.
+    ///
+    /// The returned Download owns the passed result and frees its resources
+    /// when it's dropped; when an error is returned they are freed by this
+    /// function.
+    pub(crate) fn from_uplink_c_result(download_res: ulksys::UplinkDownloadResult) -> Result<Self> {
+        Error::new_uplink(download_res.error).map_or(
+            Ok(Download {
+                inner: download_res,
+            }),
+            |err| {
+                drop_uplink_sys_download_result(download_res);
+                Err(err)
+            },
+        )
+    }
+
+    /// Returns the information about the object being downloaded.
+    pub fn info(&self) -> Result<Object> {
+        let object_res;
+        // SAFETY: we trust that the underlying c-binding is safe, nonetheless
+        // we ensure object_res is correct through the ensure method of the
+        // implemented Ensurer trait.
+        unsafe {
+            object_res = *ulksys::uplink_download_info(self.inner.download).ensure();
+        }
+
+        Object::from_uplink_c_result(object_res)
+    }
+
+    /// Closes the download.
+    ///
+    /// The download is also closed when this instance drops, but this method
+    /// allows to know if closing it produced an error.
+    pub fn close(&mut self) -> Result<()> {
+        let uerr;
+        // SAFETY: we trust that the underlying c-binding is safe.
+        unsafe {
+            uerr = ulksys::uplink_close_download(self.inner.download);
+        }
+
+        Error::new_uplink(uerr).map_or(Ok(()), |err| {
+            helpers::drop_uplink_sys_error(uerr);
+            Err(err)
+        })
+    }
+}
+
+impl io::Read for Download {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let read_res;
+        // SAFETY: we trust that the underlying c-binding is safe and it only
+        // writes up to buf.len() bytes into the pointer.
+        unsafe {
+            read_res = ulksys::uplink_download_read(
+                self.inner.download,
+                buf.as_mut_ptr() as *mut c_void,
+                buf.len(),
+            );
+        }
+
+        // SAFETY: the error is only dereferenced when it isn't NULL.
+        let res =
+            if read_res.error.is_null() || unsafe { (*read_res.error).code } == UPLINK_EOF_CODE {
+                Ok(read_res.bytes_read)
+            } else {
+                Err(io::Error::other(
+                    Error::new_uplink(read_res.error).expect("error isn't NULL"),
+                ))
+            };
+
+        drop_uplink_sys_read_result(read_res);
+        res
+    }
+}
+
+impl Drop for Download {
+    fn drop(&mut self) {
+        drop_uplink_sys_download_result(self.inner);
+    }
+}
+
+/// Options for downloading an object.
+#[derive(Debug, Default)]
+pub struct DownloadOptions {
+    /// The position of the first byte to download. When it's negative, it
+    /// downloads the last `offset` bytes of the object and `length` must be
+    /// None.
+    pub offset: i64,
+    /// The number of bytes to download. None for downloading until the end of
+    /// the object.
+    pub length: Option<u64>,
+}
+
+impl DownloadOptions {
+    /// Returns an UplinkDownloadOptions with the values of this
+    /// DownloadOptions for interoperating with the uplink c-bindings.
+    ///
+    /// It returns an error if `length` is set when `offset` is negative or if
+    /// `length` is greater than `i64::MAX`.
+    pub(crate) fn to_uplink_c(&self) -> Result<ulksys::UplinkDownloadOptions> {
+        let length = match self.length {
+            None => -1,
+            Some(_) if self.offset < 0 => {
+                return Err(Error::new_invalid_arguments(
+                    "opts{offset, length}",
+                    "length cannot be set when offset is negative",
+                ));
+            }
+            Some(l) => i64::try_from(l).map_err(|_| {
+                Error::new_invalid_arguments(
+                    "opts{length}",
+                    &format!("must be less or equal than {}", i64::MAX),
+                )
+            })?,
+        };
+
+        Ok(ulksys::UplinkDownloadOptions {
+            offset: self.offset,
+            length,
+        })
+    }
+}
+
+impl Ensurer for ulksys::UplinkDownloadResult {
+    fn ensure(&self) -> &Self {
+        assert!(!self.download.is_null() || !self.error.is_null(), "invalid underlying c-binding returned an invalid UplinkDownloadResult; download and error fields are both NULL");
+        assert!((self.download.is_null() && !self.error.is_null())
+            || (!self.download.is_null() && self.error.is_null()),
+            "invalid underlying c-binding returned an invalid UplinkDownloadResult; download and error fields are both NOT NULL");
+        self
+    }
+}
+
+/// Calls the associated `free` underlying c-bindings function for releasing
+/// the associated resources of a download result.
+fn drop_uplink_sys_download_result(download_res: ulksys::UplinkDownloadResult) {
+    // SAFETY: we trust that the underlying c-binding is safe freeing the
+    // memory of a correct UplinkDownloadResult value.
+    unsafe {
+        ulksys::uplink_free_download_result(download_res);
+    }
+}
+
+/// Calls the associated `free` underlying c-bindings function for releasing
+/// the associated resources of a read result.
+fn drop_uplink_sys_read_result(read_res: ulksys::UplinkReadResult) {
+    // SAFETY: we trust that the underlying c-binding is safe freeing the
+    // memory of a correct UplinkReadResult value.
+    unsafe {
+        ulksys::uplink_free_read_result(read_res);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::error;
+
+    use std::ptr::null_mut;
+
+    #[test]
+    fn test_download_options() {
+        {
+            // Whole object.
+            let uc_opts = DownloadOptions::default()
+                .to_uplink_c()
+                .expect("valid options");
+            assert_eq!(uc_opts.offset, 0, "offset");
+            assert_eq!(uc_opts.length, -1, "length");
+        }
+
+        {
+            // Range.
+            let uc_opts = DownloadOptions {
+                offset: 10,
+                length: Some(100),
+            }
+            .to_uplink_c()
+            .expect("valid options");
+            assert_eq!(uc_opts.offset, 10, "offset");
+            assert_eq!(uc_opts.length, 100, "length");
+        }
+
+        {
+            // Suffix.
+            let uc_opts = DownloadOptions {
+                offset: -10,
+                length: None,
+            }
+            .to_uplink_c()
+            .expect("valid options");
+            assert_eq!(uc_opts.offset, -10, "offset");
+            assert_eq!(uc_opts.length, -1, "length");
+        }
+
+        {
+            // Suffix with length.
+            let opts = DownloadOptions {
+                offset: -10,
+                length: Some(5),
+            };
+            if let Error::InvalidArguments(error::Args { names, msg }) =
+                opts.to_uplink_c().expect_err("negative offset with length")
+            {
+                assert_eq!(names, "opts{offset, length}", "invalid error argument name");
+                assert_eq!(
+                    msg, "length cannot be set when offset is negative",
+                    "invalid error argument message"
+                );
+            } else {
+                panic!("expected an invalid argument error");
+            }
+        }
+
+        {
+            // Too big length.
+            let opts = DownloadOptions {
+                offset: 0,
+                length: Some(i64::MAX as u64 + 1),
+            };
+            if let Error::InvalidArguments(error::Args { names, msg }) =
+                opts.to_uplink_c().expect_err("too big length")
+            {
+                assert_eq!(names, "opts{length}", "invalid error argument name");
+                assert_eq!(
+                    msg, "must be less or equal than 9223372036854775807",
+                    "invalid error argument message"
+                );
+            } else {
+                panic!("expected an invalid argument error");
+            }
+        }
+    }
+
+    #[test]
+    fn test_ensurer_ulksys_download_result_valid() {
+        {
+            // Has a download
+            let download_res = ulksys::UplinkDownloadResult {
+                download: &mut ulksys::UplinkDownload { _handle: 0 },
+                error: null_mut::<ulksys::UplinkError>(),
+            };
+
+            download_res.ensure();
+        }
+
+        {
+            // Has an error
+            let download_res = ulksys::UplinkDownloadResult {
+                download: null_mut::<ulksys::UplinkDownload>(),
+                error: &mut ulksys::UplinkError {
+                    code: 0,
+                    message: null_mut(),
+                },
+            };
+
+            download_res.ensure();
+        }
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "invalid underlying c-binding returned an invalid UplinkDownloadResult; download and error fields are both NULL"
+    )]
+    fn test_ensurer_ulksys_download_result_invalid_both_null() {
+        let download_res = ulksys::UplinkDownloadResult {
+            download: null_mut::<ulksys::UplinkDownload>(),
+            error: null_mut::<ulksys::UplinkError>(),
+        };
+
+        download_res.ensure();
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "invalid underlying c-binding returned an invalid UplinkDownloadResult; download and error fields are both NOT NULL"
+    )]
+    fn test_ensurer_ulksys_download_result_invalid_both_not_null() {
+        let download_res = ulksys::UplinkDownloadResult {
+            download: &mut ulksys::UplinkDownload { _handle: 0 },
+            error: &mut ulksys::UplinkError {
+                code: 0,
+                message: null_mut(),
+            },
+        };
+
+        download_res.ensure();
+    }
+}
@@ -1,14 +1,182 @@
 //! Storj DCS Encryption key
 
+use crate::{helpers, Ensurer, Error, Result};
+
+use std::os::raw::c_void;
+
 use uplink_sys as ulksys;
 
-/// TODO: implement & document it
+/// Key used for encrypting and decrypting the objects' data and metadata.
+///
+/// It's mainly used for overriding the encryption key of a specific bucket
+/// and prefix of an access grant through
+/// [`Grant::override_encryption_key`](crate::access::Grant::override_encryption_key),
+/// which allows to implement multitenancy in a single app bucket.
 pub struct EncryptionKey {
+    /// The encryption key type of the underlying c-bindings Rust crate that an
+    /// instance of this struct represents and guard its life time until this
+    /// instance drops.
+    /// It's an encryption key result because it's the one that holds the
+    /// encryption key and allows to free its memory.
     inner: ulksys::UplinkEncryptionKeyResult,
 }
 
 impl EncryptionKey {
+    /// Derives a salted encryption key from the passphrase.
+    ///
+    /// The same passphrase and salt always derive the same key, so they
+    /// must be kept for decrypting the data encrypted with it.
+    ///
+    /// It returns an error if `passphrase` contains a null character (0 byte).
+    pub fn derive(passphrase: &str, salt: &[u8]) -> Result<Self> {
+        let c_passphrase = helpers::cstring_from_str_fn_arg("passphrase", passphrase)?;
+
+        let enckey_res;
+        // SAFETY: we trust that the underlying c-binding is safe and it only
+        // reads salt.len() bytes from the pointer without modifying them,
+        // nonetheless we ensure enckey_res is correct through the ensure
+        // method of the implemented Ensurer trait.
+        unsafe {
+            enckey_res = *ulksys::uplink_derive_encryption_key(
+                c_passphrase.as_ptr(),
+                salt.as_ptr() as *mut c_void,
+                salt.len(),
+            )
+            .ensure();
+        }
+
+        Error::new_uplink(enckey_res.error).map_or(Ok(EncryptionKey { inner: enckey_res }), |err| {
+            drop_uplink_sys_encryption_key_result(enckey_res);
+            Err(err)
+        })
+    }
+
+    /// Returns the underlying c-bindings representation of this encryption
+    /// key. The returned pointer is valid as long as `self` is.
     pub(crate) fn as_uplink_c(&self) -> *mut ulksys::UplinkEncryptionKey {
         self.inner.encryption_key
     }
 }
+
+impl Drop for EncryptionKey {
+    fn drop(&mut self) {
+        drop_uplink_sys_encryption_key_result(self.inner);
+    }
+}
+
+impl Ensurer for ulksys::UplinkEncryptionKeyResult {
+    fn ensure(&self) -> &Self {
+        assert!(!self.encryption_key.is_null() || !self.error.is_null(), "invalid underlying c-binding returned an invalid UplinkEncryptionKeyResult; encryption_key and error fields are both NULL");
+        assert!((self.encryption_key.is_null() && !self.error.is_null())
+            || (!self.encryption_key.is_null() && self.error.is_null()),
+            "invalid underlying c-binding returned an invalid UplinkEncryptionKeyResult; encryption_key and error fields are both NOT NULL");
+        self
+    }
+}
+
+/// Calls the associated `free` underlying c-bindings function for releasing
+/// the associated resources of an encryption key result.
+fn drop_uplink_sys_encryption_key_result(enckey_res: ulksys::UplinkEncryptionKeyResult) {
+    // SAFETY: we trust that the underlying c-binding is safe freeing the
+    // memory of a correct UplinkEncryptionKeyResult value.
+    unsafe {
+        ulksys::uplink_free_encryption_key_result(enckey_res);
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod test {
+    use super::*;
+    use crate::error;
+    use crate::helpers::test::fake_uplink_c_wrapper;
+
+    use std::mem::ManuallyDrop;
+    use std::ptr::null_mut;
+
+    /// Returns an EncryptionKey which isn't dropped and it doesn't hold a
+    /// valid key, so it can only be used for testing the functions that fail
+    /// before passing it to the underlying c-bindings.
+    pub(crate) fn fake_encryption_key() -> ManuallyDrop<EncryptionKey> {
+        fake_uplink_c_wrapper(
+            ulksys::UplinkEncryptionKey { _handle: 0 },
+            |encryption_key| EncryptionKey {
+                inner: ulksys::UplinkEncryptionKeyResult {
+                    encryption_key,
+                    error: null_mut(),
+                },
+            },
+        )
+    }
+
+    #[test]
+    fn test_encryption_key_derive_invalid_params() {
+        if let Error::InvalidArguments(error::Args { names, msg }) =
+            EncryptionKey::derive("pass\0phrase", b"salt")
+                .err()
+                .expect("when passing a passphrase with NULL bytes")
+        {
+            assert_eq!(names, "passphrase", "invalid error argument name");
+            assert_eq!(
+                msg, "cannot contains null bytes (0 byte). Null byte found at 4",
+                "invalid error argument message"
+            );
+        } else {
+            panic!("expected an invalid argument error");
+        }
+    }
+
+    #[test]
+    fn test_ensurer_ulksys_encryption_key_result_valid() {
+        {
+            // Has an encryption key
+            let enckey_res = ulksys::UplinkEncryptionKeyResult {
+                encryption_key: &mut ulksys::UplinkEncryptionKey { _handle: 0 },
+                error: null_mut::<ulksys::UplinkError>(),
+            };
+
+            enckey_res.ensure();
+        }
+
+        {
+            // Has an error
+            let enckey_res = ulksys::UplinkEncryptionKeyResult {
+                encryption_key: null_mut::<ulksys::UplinkEncryptionKey>(),
+                error: &mut ulksys::UplinkError {
+                    code: 0,
+                    message: null_mut(),
+                },
+            };
+
+            enckey_res.ensure();
+        }
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "invalid underlying c-binding returned an invalid UplinkEncryptionKeyResult; encryption_key and error fields are both NULL"
+    )]
+    fn test_ensurer_ulksys_encryption_key_result_invalid_both_null() {
+        let enckey_res = ulksys::UplinkEncryptionKeyResult {
+            encryption_key: null_mut::<ulksys::UplinkEncryptionKey>(),
+            error: null_mut::<ulksys::UplinkError>(),
+        };
+
+        enckey_res.ensure();
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "invalid underlying c-binding returned an invalid UplinkEncryptionKeyResult; encryption_key and error fields are both NOT NULL"
+    )]
+    fn test_ensurer_ulksys_encryption_key_result_invalid_both_not_null() {
+        let enckey_res = ulksys::UplinkEncryptionKeyResult {
+            encryption_key: &mut ulksys::UplinkEncryptionKey { _handle: 0 },
+            error: &mut ulksys::UplinkError {
+                code: 0,
+                message: null_mut(),
+            },
+        };
+
+        enckey_res.ensure();
+    }
+}
@@ -200,6 +200,9 @@ impl Uplink {
             ulksys::UPLINK_ERROR_INVALID_HANDLE => "invalid handle",
             ulksys::UPLINK_ERROR_TOO_MANY_REQUESTS => "too many requests",
             ulksys::UPLINK_ERROR_BANDWIDTH_LIMIT_EXCEEDED => "bandwidth limit exceeded",
+            ulksys::UPLINK_ERROR_STORAGE_LIMIT_EXCEEDED => "storage limit exceeded",
+            ulksys::UPLINK_ERROR_SEGMENTS_LIMIT_EXCEEDED => "segments limit exceeded",
+            ulksys::UPLINK_ERROR_PERMISSION_DENIED => "permission denied",
             ulksys::UPLINK_ERROR_BUCKET_NAME_INVALID => "invalid bucket name",
             ulksys::UPLINK_ERROR_BUCKET_ALREADY_EXISTS => "bucket already exists",
             ulksys::UPLINK_ERROR_BUCKET_NOT_EMPTY => "bucket not empty",
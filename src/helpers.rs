@@ -3,6 +3,9 @@
 use crate::Error;
 
 use std::ffi::CString;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use uplink_sys as ulksys;
 
 /// creates a CString from a function &str function argument and if there is an
 /// error it returnns an Error::InvalidArguments with the passed argument's
@@ -19,10 +22,97 @@ pub fn cstring_from_str_fn_arg(arg_name: &str, arg_val: &str) -> Result<CString,
     })
 }
 
+/// Calls, only if `error` is not null,  the associated `free` underlying
+/// c-bindings function for releasing the associated resources with `error` and
+/// to free the memory pointed by it.
+pub fn drop_uplink_sys_error(error: *mut ulksys::UplinkError) {
+    if !error.is_null() {
+        // SAFETY: We just checked that the pointer is not null and we trust
+        // that the underlying c-binding is safe freeing its associated
+        // resources and itself.
+        unsafe {
+            ulksys::uplink_free_error(error);
+        }
+    }
+}
+
+/// Converts the number of seconds since the Unix Epoch time, which is how the
+/// underlying c-bindings represent times, to a SystemTime.
+/// It returns None if `secs` is negative or the system time cannot represent
+/// it.
+pub fn system_time_from_unix_secs(secs: i64) -> Option<SystemTime> {
+    if secs < 0 {
+        return None;
+    }
+
+    UNIX_EPOCH.checked_add(Duration::from_secs(secs as u64))
+}
+
+/// Converts a SystemTime to the number of seconds since the Unix Epoch time,
+/// which is how the underlying c-bindings represent times, discarding the
+/// fraction of a second.
+/// It returns None if `time` is before the Unix Epoch time or the number of
+/// seconds doesn't fit in an i64.
+pub fn unix_secs_from_system_time(time: SystemTime) -> Option<i64> {
+    let since_epoch = time.duration_since(UNIX_EPOCH).ok()?;
+    i64::try_from(since_epoch.as_secs()).ok()
+}
+
+/// Checks that `time` can be passed to the underlying c-bindings, which
+/// represent times with the number of seconds since the Unix Epoch time and
+/// the Unix Epoch time itself as no time, without losing precision.
+/// It returns an invalid arguments error for the argument `name` if `time`
+/// isn't after the Unix Epoch time, it has a fraction of a second or the
+/// number of seconds doesn't fit in an i64.
+pub fn check_unix_secs_time(name: &str, time: SystemTime) -> Result<(), Error> {
+    let since_epoch = match time.duration_since(UNIX_EPOCH) {
+        Ok(d) if !d.is_zero() => d,
+        _ => {
+            return Err(Error::new_invalid_arguments(
+                name,
+                "cannot be the Unix Epoch time or before it",
+            ))
+        }
+    };
+
+    if since_epoch.subsec_nanos() != 0 {
+        return Err(Error::new_invalid_arguments(
+            name,
+            "cannot have a fraction of a second",
+        ));
+    }
+
+    if unix_secs_from_system_time(time).is_none() {
+        return Err(Error::new_invalid_arguments(
+            name,
+            "cannot be represented with the number of seconds since the Unix Epoch time",
+        ));
+    }
+
+    Ok(())
+}
+
 #[cfg(test)]
-mod test {
+pub(crate) mod test {
     use super::*;
 
+    use std::mem::ManuallyDrop;
+
+    /// Returns a value of a type that wraps an underlying c-bindings handle,
+    /// built by `wrap` with a pointer to `handle`, for testing the functions
+    /// that fail before passing the handle to the underlying c-bindings.
+    ///
+    /// The handle isn't allocated by the underlying c-bindings, so the
+    /// returned value is never dropped, because its `Drop` implementation
+    /// would pass the handle to their `free` function; hence the handle is
+    /// leaked on purpose.
+    pub(crate) fn fake_uplink_c_wrapper<H, T>(
+        handle: H,
+        wrap: impl FnOnce(*mut H) -> T,
+    ) -> ManuallyDrop<T> {
+        ManuallyDrop::new(wrap(Box::into_raw(Box::new(handle))))
+    }
+
     #[test]
     fn test_cstring_from_str_fn_arg() {
         let val = cstring_from_str_fn_arg("some", "this is fine")
@@ -48,4 +138,45 @@ mod test {
             panic!("expected an Error::InvalidArguments");
         }
     }
+
+    #[test]
+    fn test_system_time_from_unix_secs() {
+        assert_eq!(
+            system_time_from_unix_secs(0),
+            Some(UNIX_EPOCH),
+            "Unix Epoch time"
+        );
+        assert_eq!(
+            system_time_from_unix_secs(1_600_000_000),
+            Some(UNIX_EPOCH + Duration::from_secs(1_600_000_000)),
+            "after Unix Epoch time"
+        );
+        assert_eq!(
+            system_time_from_unix_secs(-1),
+            None,
+            "before Unix Epoch time"
+        );
+        // It's None, rather than a panic, where the system time cannot
+        // represent it.
+        let _ = system_time_from_unix_secs(i64::MAX);
+    }
+
+    #[test]
+    fn test_unix_secs_from_system_time() {
+        assert_eq!(
+            unix_secs_from_system_time(UNIX_EPOCH),
+            Some(0),
+            "Unix Epoch time"
+        );
+        assert_eq!(
+            unix_secs_from_system_time(UNIX_EPOCH + Duration::new(1_600_000_000, 999)),
+            Some(1_600_000_000),
+            "after Unix Epoch time"
+        );
+        assert_eq!(
+            unix_secs_from_system_time(UNIX_EPOCH - Duration::from_secs(1)),
+            None,
+            "before Unix Epoch time"
+        );
+    }
 }
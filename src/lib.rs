@@ -9,6 +9,11 @@ pub(crate) mod project;
 
 pub mod access;
 pub mod bucket;
+pub mod download;
+pub mod multipart;
+pub mod object;
+pub mod tenant;
+pub mod upload;
 pub use encryption_key::EncryptionKey;
 pub use error::Error;
 pub use project::Project;
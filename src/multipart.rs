@@ -0,0 +1,1003 @@
+//! Storj DCS multipart upload and related types.
+//!
+//! A multipart upload allows to upload an object in several parts, which can
+//! be uploaded in parallel, even from different processes or machines, and
+//! retried independently. It starts with [`crate::Project::begin_upload`],
+//! each part is uploaded with [`crate::Project::upload_part`] and it finishes
+//! with [`crate::Project::commit_upload`] or
+//! [`crate::Project::abort_upload`].
+//!
+//! The pending multipart uploads of a bucket are listed with
+//! [`crate::Project::list_uploads`] and the parts of one of them with
+//! [`crate::Project::list_upload_parts`].
+
+use crate::object::{CustomMetadata, SystemMetadata};
+use crate::upload;
+use crate::{helpers, Ensurer, Error, Result};
+
+use std::ffi::{CStr, CString};
+use std::io;
+use std::os::raw::c_void;
+use std::time::SystemTime;
+
+use uplink_sys as ulksys;
+
+/// Contains information about a multipart upload.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UploadInfo {
+    /// The identifier of the multipart upload, which is required for
+    /// uploading its parts and committing or aborting it.
+    pub upload_id: String,
+    /// The identifier of the object inside of the bucket.
+    pub key: String,
+    /// Indicates if the object is a prefix for other objects.
+    pub is_prefix: bool,
+    /// The system metadata associated with the object.
+    pub system: SystemMetadata,
+    /// The custom metadata associated with the object.
+    pub custom: CustomMetadata,
+}
+
+impl UploadInfo {
+    /// Creates an UploadInfo instance from the type exposed by the uplink
+    /// c-bindings.
+    ///
+    /// The returned UploadInfo has a copy of the values of the passed pointer
+    /// and this function frees it, hence the caller should not use that
+    /// pointer after this call nor free it, even when an error is returned,
+    /// unless it's null.
+    pub(crate) fn from_uplink_c(uc_info: *mut ulksys::UplinkUploadInfo) -> Result<Self> {
+        if uc_info.is_null() {
+            return Err(Error::new_invalid_arguments("uc_info", "cannot be null"));
+        }
+
+        let info;
+        // SAFETY: uc_info cannot be null because it's checked at the beginning
+        // of the function and we ensure uc_info doesn't have fields with NULL
+        // pointers through the ensure method of the implemented Ensurer trait.
+        // uc_info isn't used after it's freed.
+        unsafe {
+            info = Self::from_ensured_uplink_c((*uc_info).ensure());
+            ulksys::uplink_free_upload_info(uc_info);
+        }
+
+        info
+    }
+
+    /// Creates an UploadInfo instance from an ensured upload info result
+    /// returned by the uplink c-bindings or an error if the result contains
+    /// one.
+    ///
+    /// The resources associated with the passed result are freed by this
+    /// function, hence the caller should not use them after this call.
+    pub(crate) fn from_uplink_c_result(info_res: ulksys::UplinkUploadInfoResult) -> Result<Self> {
+        if let Some(err) = Error::new_uplink(info_res.error) {
+            drop_uplink_sys_upload_info_result(info_res);
+            return Err(err);
+        }
+
+        Self::from_uplink_c(info_res.info)
+    }
+
+    /// Creates an UploadInfo instance copying the values of an ensured upload
+    /// info of the uplink c-bindings.
+    fn from_ensured_uplink_c(uc_info: &ulksys::UplinkUploadInfo) -> Result<Self> {
+        // SAFETY: the caller ensures that upload_id isn't NULL and the
+        // underlying c-binding returns NULL terminated strings.
+        let upload_id = match unsafe { CStr::from_ptr(uc_info.upload_id) }.to_str() {
+            Ok(id) => String::from(id),
+            Err(err) => {
+                return Err(Error::new_internal_with_inner(
+                    "invalid upload ID because it contains invalid UTF-8 characters",
+                    err.into(),
+                ));
+            }
+        };
+
+        // SAFETY: the caller ensures that key isn't NULL and the underlying
+        // c-binding returns NULL terminated strings.
+        let key = match unsafe { CStr::from_ptr(uc_info.key) }.to_str() {
+            Ok(k) => String::from(k),
+            Err(err) => {
+                return Err(Error::new_internal_with_inner(
+                    "invalid object key because it contains invalid UTF-8 characters",
+                    err.into(),
+                ));
+            }
+        };
+
+        Ok(UploadInfo {
+            upload_id,
+            key,
+            is_prefix: uc_info.is_prefix,
+            system: SystemMetadata::from_uplink_c(&uc_info.system)?,
+            custom: CustomMetadata::from_uplink_c(&uc_info.custom)?,
+        })
+    }
+}
+
+/// Uploads a part of a multipart upload.
+///
+/// The content of the part is written through the implementation of the
+/// [`std::io::Write`] trait and the part upload has to be committed for making
+/// the part available to the multipart upload; otherwise it has to be
+/// aborted.
+#[derive(Debug)]
+pub struct PartUpload {
+    /// The part upload type of the underlying c-bindings Rust crate that an
+    /// instance of this struct represents and guard its life time until this
+    /// instance drops.
+    /// It's a part upload result because it's the one that holds the part
+    /// upload and allows to free its memory.
+    inner: ulksys::UplinkPartUploadResult,
+}
+
+impl PartUpload {
+    /// Creates a PartUpload instance from an ensured part upload result
+    /// returned by the uplink c-bindings or an error if the result contains
+    /// one.
+    ///
+    /// The returned PartUpload owns the passed result and frees its resources
+    /// when it's dropped; when an error is returned they are freed by this
+    /// function.
+    pub(crate) fn from_uplink_c_result(
+        part_upload_res: ulksys::UplinkPartUploadResult,
+    ) -> Result<Self> {
+        Error::new_uplink(part_upload_res.error).map_or(
+            Ok(PartUpload {
+                inner: part_upload_res,
+            }),
+            |err| {
+                drop_uplink_sys_part_upload_result(part_upload_res);
+                Err(err)
+            },
+        )
+    }
+
+    /// Commits the uploaded data making the part available to the multipart
+    /// upload.
+    pub fn commit(&mut self) -> Result<()> {
+        let uerr;
+        // SAFETY: we trust that the underlying c-binding is safe.
+        unsafe {
+            uerr = ulksys::uplink_part_upload_commit(self.inner.part_upload);
+        }
+
+        Error::new_uplink(uerr).map_or(Ok(()), |err| {
+            helpers::drop_uplink_sys_error(uerr);
+            Err(err)
+        })
+    }
+
+    /// Aborts the part upload discarding the uploaded data.
+    pub fn abort(&mut self) -> Result<()> {
+        let uerr;
+        // SAFETY: we trust that the underlying c-binding is safe.
+        unsafe {
+            uerr = ulksys::uplink_part_upload_abort(self.inner.part_upload);
+        }
+
+        Error::new_uplink(uerr).map_or(Ok(()), |err| {
+            helpers::drop_uplink_sys_error(uerr);
+            Err(err)
+        })
+    }
+
+    /// Sets the ETag of the part, which is stored when the part upload is
+    /// committed.
+    ///
+    /// It returns an error if `etag` contains a null character (0 byte).
+    pub fn set_etag(&mut self, etag: &str) -> Result<()> {
+        let c_etag = helpers::cstring_from_str_fn_arg("etag", etag)?;
+        let uerr;
+        // SAFETY: we trust that the underlying c-binding is safe.
+        unsafe {
+            uerr = ulksys::uplink_part_upload_set_etag(self.inner.part_upload, c_etag.as_ptr());
+        }
+
+        Error::new_uplink(uerr).map_or(Ok(()), |err| {
+            helpers::drop_uplink_sys_error(uerr);
+            Err(err)
+        })
+    }
+
+    /// Returns the information about the part being uploaded.
+    pub fn info(&self) -> Result<Part> {
+        let part_res;
+        // SAFETY: we trust that the underlying c-binding is safe, nonetheless
+        // we ensure part_res is correct through the ensure method of the
+        // implemented Ensurer trait.
+        unsafe {
+            part_res = *ulksys::uplink_part_upload_info(self.inner.part_upload).ensure();
+        }
+
+        if let Some(err) = Error::new_uplink(part_res.error) {
+            drop_uplink_sys_part_result(part_res);
+            return Err(err);
+        }
+
+        Part::from_uplink_c(part_res.part)
+    }
+}
+
+impl io::Write for PartUpload {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let write_res;
+        // SAFETY: we trust that the underlying c-binding is safe and it only
+        // reads buf.len() bytes from the pointer.
+        unsafe {
+            write_res = ulksys::uplink_part_upload_write(
+                self.inner.part_upload,
+                buf.as_ptr() as *mut c_void,
+                buf.len(),
+            );
+        }
+
+        let res = Error::new_uplink(write_res.error).map_or(Ok(write_res.bytes_written), |err| {
+            Err(io::Error::other(err))
+        });
+        upload::drop_uplink_sys_write_result(write_res);
+        res
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        // The underlying c-binding doesn't buffer any data.
+        Ok(())
+    }
+}
+
+impl Drop for PartUpload {
+    fn drop(&mut self) {
+        drop_uplink_sys_part_upload_result(self.inner);
+    }
+}
+
+/// Contains information about a part of a multipart upload.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Part {
+    /// The number of the part in the multipart upload.
+    pub part_number: u32,
+    /// The size of the part content in bytes.
+    pub size: u64,
+    /// Time when the part was last modified.
+    pub modified: SystemTime,
+    /// The ETag of the part. It's empty when it hasn't been set.
+    pub etag: String,
+}
+
+impl Part {
+    /// Creates a Part instance from the type exposed by the uplink c-bindings.
+    ///
+    /// The returned Part has a copy of the values of the passed pointer and
+    /// this function frees it, hence the caller should not use that pointer
+    /// after this call nor free it, even when an error is returned, unless
+    /// it's null.
+    pub(crate) fn from_uplink_c(uc_part: *mut ulksys::UplinkPart) -> Result<Self> {
+        if uc_part.is_null() {
+            return Err(Error::new_invalid_arguments("uc_part", "cannot be null"));
+        }
+
+        let part;
+        // SAFETY: uc_part cannot be null because it's checked at the beginning
+        // of the function and we ensure uc_part doesn't have fields with NULL
+        // pointers through the ensure method of the implemented Ensurer trait.
+        // uc_part isn't used after it's freed.
+        unsafe {
+            part = Self::from_ensured_uplink_c((*uc_part).ensure());
+            ulksys::uplink_free_part(uc_part);
+        }
+
+        part
+    }
+
+    /// Creates a Part instance copying the values of an ensured part of the
+    /// uplink c-bindings.
+    fn from_ensured_uplink_c(uc_part: &ulksys::UplinkPart) -> Result<Self> {
+        // A part without ETag may have a NULL etag.
+        let etag = if uc_part.etag_length == 0 {
+            String::new()
+        } else {
+            // SAFETY: the caller ensures that etag isn't NULL when etag_length
+            // isn't 0 and the underlying c-binding returns etag_length bytes
+            // in it.
+            match std::str::from_utf8(unsafe {
+                std::slice::from_raw_parts(uc_part.etag as *const u8, uc_part.etag_length)
+            }) {
+                Ok(e) => String::from(e),
+                Err(err) => {
+                    return Err(Error::new_internal_with_inner(
+                        "invalid part ETag because it contains invalid UTF-8 characters",
+                        err.into(),
+                    ));
+                }
+            }
+        };
+
+        let modified = helpers::system_time_from_unix_secs(uc_part.modified).ok_or_else(|| {
+            Error::new_internal(
+                "invalid part modification time because it's before the Unix Epoch time or out of range",
+            )
+        })?;
+
+        Ok(Part {
+            part_number: uc_part.part_number,
+            size: uc_part.size as u64,
+            modified,
+            etag,
+        })
+    }
+}
+
+/// Options for committing a multipart upload.
+#[derive(Debug, Default)]
+pub struct CommitUploadOptions {
+    /// The custom metadata to associate with the object.
+    pub custom_metadata: CustomMetadata,
+}
+
+/// Iterates over a collection of pending multipart uploads.
+pub struct UploadIterator {
+    /// The upload iterator type of the underlying c-bindings Rust crate that
+    /// an instance of this struct represents and guard its life time until this
+    /// instance drops.
+    inner: *mut ulksys::UplinkUploadIterator,
+    /// The object key of the last upload returned by the iterator.
+    cursor: Option<String>,
+    /// Indicates that the underlying c-bindings iterator is exhausted, so the
+    /// error, if any, is only returned once.
+    done: bool,
+}
+
+impl UploadIterator {
+    /// Creates an UploadIterator instance from the type exposed by the uplink
+    /// c-bindings.
+    pub(crate) fn from_uplink_c(uc_iterator: *mut ulksys::UplinkUploadIterator) -> Result<Self> {
+        if uc_iterator.is_null() {
+            return Err(Error::new_invalid_arguments(
+                "uc_iterator",
+                "cannot be null",
+            ));
+        }
+
+        Ok(UploadIterator {
+            inner: uc_iterator,
+            cursor: None,
+            done: false,
+        })
+    }
+
+    /// Returns the object key of the last upload returned by this iterator or
+    /// None if it hasn't returned any yet.
+    ///
+    /// The returned value can be used as the cursor of [`ListUploadsOptions`]
+    /// for listing the uploads after it.
+    pub fn cursor(&self) -> Option<&str> {
+        self.cursor.as_deref()
+    }
+}
+
+impl std::iter::Iterator for UploadIterator {
+    type Item = Result<UploadInfo>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        // SAFETY: we trust that the underlying c-binding is safe and
+        // self.inner is never NULL because it's checked by the constructor.
+        unsafe {
+            if !ulksys::uplink_upload_iterator_next(self.inner) {
+                self.done = true;
+                let uc_error = ulksys::uplink_upload_iterator_err(self.inner);
+                return Error::new_uplink(uc_error).map(|err| {
+                    helpers::drop_uplink_sys_error(uc_error);
+                    Err(err)
+                });
+            }
+
+            let info = UploadInfo::from_uplink_c(ulksys::uplink_upload_iterator_item(self.inner));
+            if let Ok(i) = &info {
+                self.cursor = Some(i.key.clone());
+            }
+
+            Some(info)
+        }
+    }
+}
+
+impl Drop for UploadIterator {
+    fn drop(&mut self) {
+        // SAFETY: we trust that the underlying c-binding is safe freeing the
+        // memory of a correct UplinkUploadIterator value.
+        unsafe {
+            ulksys::uplink_free_upload_iterator(self.inner);
+        }
+    }
+}
+
+/// Iterates over the collection of parts of a multipart upload.
+pub struct PartIterator {
+    /// The part iterator type of the underlying c-bindings Rust crate that an
+    /// instance of this struct represents and guard its life time until this
+    /// instance drops.
+    inner: *mut ulksys::UplinkPartIterator,
+    /// The part number of the last part returned by the iterator.
+    cursor: Option<u32>,
+    /// Indicates that the underlying c-bindings iterator is exhausted, so the
+    /// error, if any, is only returned once.
+    done: bool,
+}
+
+impl PartIterator {
+    /// Creates a PartIterator instance from the type exposed by the uplink
+    /// c-bindings.
+    pub(crate) fn from_uplink_c(uc_iterator: *mut ulksys::UplinkPartIterator) -> Result<Self> {
+        if uc_iterator.is_null() {
+            return Err(Error::new_invalid_arguments(
+                "uc_iterator",
+                "cannot be null",
+            ));
+        }
+
+        Ok(PartIterator {
+            inner: uc_iterator,
+            cursor: None,
+            done: false,
+        })
+    }
+
+    /// Returns the part number of the last part returned by this iterator or
+    /// None if it hasn't returned any yet.
+    ///
+    /// The returned value can be used as the cursor of
+    /// [`crate::Project::list_upload_parts`] for listing the parts after it.
+    pub fn cursor(&self) -> Option<u32> {
+        self.cursor
+    }
+}
+
+impl std::iter::Iterator for PartIterator {
+    type Item = Result<Part>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        // SAFETY: we trust that the underlying c-binding is safe and
+        // self.inner is never NULL because it's checked by the constructor.
+        unsafe {
+            if !ulksys::uplink_part_iterator_next(self.inner) {
+                self.done = true;
+                let uc_error = ulksys::uplink_part_iterator_err(self.inner);
+                return Error::new_uplink(uc_error).map(|err| {
+                    helpers::drop_uplink_sys_error(uc_error);
+                    Err(err)
+                });
+            }
+
+            let part = Part::from_uplink_c(ulksys::uplink_part_iterator_item(self.inner));
+            if let Ok(p) = &part {
+                self.cursor = Some(p.part_number);
+            }
+
+            Some(part)
+        }
+    }
+}
+
+impl Drop for PartIterator {
+    fn drop(&mut self) {
+        // SAFETY: we trust that the underlying c-binding is safe freeing the
+        // memory of a correct UplinkPartIterator value.
+        unsafe {
+            ulksys::uplink_free_part_iterator(self.inner);
+        }
+    }
+}
+
+/// Options for listing pending multipart uploads.
+#[derive(Debug)]
+pub struct ListUploadsOptions<'a> {
+    prefix: &'a str,
+    c_prefix: CString,
+    cursor: &'a str,
+    c_cursor: CString,
+    /// Lists the uploads of all the prefixes under the listed prefix rather
+    /// than collapsing them into prefixes.
+    pub recursive: bool,
+    /// Includes the system metadata of the listed uploads.
+    pub system: bool,
+    /// Includes the custom metadata of the listed uploads.
+    pub custom: bool,
+}
+
+impl<'a> ListUploadsOptions<'a> {
+    /// Creates options for listing the uploads under `prefix` starting after
+    /// the upload of the object key `cursor`; the first listed upload is the
+    /// one after the cursor.
+    ///
+    /// A non-empty prefix must end with a slash (`/`). Empty values list all
+    /// the uploads of the bucket from the beginning.
+    ///
+    /// The options don't list recursively nor include any metadata unless
+    /// the respective fields are set.
+    ///
+    /// It returns an error if prefix or cursor contains a null character
+    /// (0 byte).
+    pub fn new(prefix: &'a str, cursor: &'a str) -> Result<Self> {
+        let c_prefix = helpers::cstring_from_str_fn_arg("prefix", prefix)?;
+        let c_cursor = helpers::cstring_from_str_fn_arg("cursor", cursor)?;
+
+        Ok(ListUploadsOptions {
+            prefix,
+            c_prefix,
+            cursor,
+            c_cursor,
+            recursive: false,
+            system: false,
+            custom: false,
+        })
+    }
+
+    /// Returns the prefix of the listed uploads.
+    pub fn prefix(&self) -> &str {
+        self.prefix
+    }
+
+    /// Returns the object key of the upload after which the listing starts.
+    pub fn cursor(&self) -> &str {
+        self.cursor
+    }
+
+    /// Returns an UplinkListUploadsOptions with the values of this
+    /// ListUploadsOptions for interoperating with the uplink c-bindings.
+    /// The pointer fields of the returned struct will be valid as long as
+    /// `self` is.
+    pub(crate) fn as_uplink_c(&self) -> ulksys::UplinkListUploadsOptions {
+        ulksys::UplinkListUploadsOptions {
+            prefix: self.c_prefix.as_ptr(),
+            cursor: self.c_cursor.as_ptr(),
+            recursive: self.recursive,
+            system: self.system,
+            custom: self.custom,
+        }
+    }
+}
+
+impl Ensurer for ulksys::UplinkUploadInfoResult {
+    fn ensure(&self) -> &Self {
+        assert!(!self.info.is_null() || !self.error.is_null(), "invalid underlying c-binding returned an invalid UplinkUploadInfoResult; info and error fields are both NULL");
+        assert!((self.info.is_null() && !self.error.is_null())
+            || (!self.info.is_null() && self.error.is_null()),
+            "invalid underlying c-binding returned an invalid UplinkUploadInfoResult; info and error fields are both NOT NULL");
+        self
+    }
+}
+
+impl Ensurer for ulksys::UplinkUploadInfo {
+    fn ensure(&self) -> &Self {
+        assert!(
+            !self.upload_id.is_null(),
+            "invalid underlying c-binding returned invalid UplinkUploadInfo; upload_id field is NULL"
+        );
+        assert!(
+            !self.key.is_null(),
+            "invalid underlying c-binding returned invalid UplinkUploadInfo; key field is NULL"
+        );
+        self
+    }
+}
+
+impl Ensurer for ulksys::UplinkPartUploadResult {
+    fn ensure(&self) -> &Self {
+        assert!(!self.part_upload.is_null() || !self.error.is_null(), "invalid underlying c-binding returned an invalid UplinkPartUploadResult; part_upload and error fields are both NULL");
+        assert!((self.part_upload.is_null() && !self.error.is_null())
+            || (!self.part_upload.is_null() && self.error.is_null()),
+            "invalid underlying c-binding returned an invalid UplinkPartUploadResult; part_upload and error fields are both NOT NULL");
+        self
+    }
+}
+
+impl Ensurer for ulksys::UplinkPartResult {
+    fn ensure(&self) -> &Self {
+        assert!(!self.part.is_null() || !self.error.is_null(), "invalid underlying c-binding returned an invalid UplinkPartResult; part and error fields are both NULL");
+        assert!((self.part.is_null() && !self.error.is_null())
+            || (!self.part.is_null() && self.error.is_null()),
+            "invalid underlying c-binding returned an invalid UplinkPartResult; part and error fields are both NOT NULL");
+        self
+    }
+}
+
+impl Ensurer for ulksys::UplinkPart {
+    fn ensure(&self) -> &Self {
+        assert!(
+            self.etag_length == 0 || !self.etag.is_null(),
+            "invalid underlying c-binding returned invalid UplinkPart; etag field is NULL"
+        );
+        self
+    }
+}
+
+impl Ensurer for ulksys::UplinkCommitUploadResult {
+    fn ensure(&self) -> &Self {
+        assert!(!self.object.is_null() || !self.error.is_null(), "invalid underlying c-binding returned an invalid UplinkCommitUploadResult; object and error fields are both NULL");
+        assert!((self.object.is_null() && !self.error.is_null())
+            || (!self.object.is_null() && self.error.is_null()),
+            "invalid underlying c-binding returned an invalid UplinkCommitUploadResult; object and error fields are both NOT NULL");
+        self
+    }
+}
+
+/// Calls the associated `free` underlying c-bindings function for releasing
+/// the associated resources of an upload info result.
+fn drop_uplink_sys_upload_info_result(info_res: ulksys::UplinkUploadInfoResult) {
+    // SAFETY: we trust that the underlying c-binding is safe freeing the
+    // memory of a correct UplinkUploadInfoResult value.
+    unsafe {
+        ulksys::uplink_free_upload_info_result(info_res);
+    }
+}
+
+/// Calls the associated `free` underlying c-bindings function for releasing
+/// the associated resources of a part upload result.
+fn drop_uplink_sys_part_upload_result(part_upload_res: ulksys::UplinkPartUploadResult) {
+    // SAFETY: we trust that the underlying c-binding is safe freeing the
+    // memory of a correct UplinkPartUploadResult value.
+    unsafe {
+        ulksys::uplink_free_part_upload_result(part_upload_res);
+    }
+}
+
+/// Calls the associated `free` underlying c-bindings function for releasing
+/// the associated resources of a part result.
+fn drop_uplink_sys_part_result(part_res: ulksys::UplinkPartResult) {
+    // SAFETY: we trust that the underlying c-binding is safe freeing the
+    // memory of a correct UplinkPartResult value.
+    unsafe {
+        ulksys::uplink_free_part_result(part_res);
+    }
+}
+
+/// Calls the associated `free` underlying c-bindings function for releasing
+/// the associated resources of a commit upload result.
+pub(crate) fn drop_uplink_sys_commit_upload_result(commit_res: ulksys::UplinkCommitUploadResult) {
+    // SAFETY: we trust that the underlying c-binding is safe freeing the
+    // memory of a correct UplinkCommitUploadResult value.
+    unsafe {
+        ulksys::uplink_free_commit_upload_result(commit_res);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::error;
+
+    use std::ptr::null_mut;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    #[test]
+    fn test_upload_info_from_ensured_uplink_c() {
+        let upload_id = CString::new("an-upload-id").unwrap();
+        let key = CString::new("a/key").unwrap();
+        let info = UploadInfo::from_ensured_uplink_c(&ulksys::UplinkUploadInfo {
+            upload_id: upload_id.as_ptr() as *mut _,
+            key: key.as_ptr() as *mut _,
+            is_prefix: false,
+            system: ulksys::UplinkSystemMetadata {
+                created: 1_600_000_000,
+                expires: 0,
+                content_length: 0,
+            },
+            custom: ulksys::UplinkCustomMetadata {
+                entries: null_mut(),
+                count: 0,
+            },
+        })
+        .expect("valid upload info");
+
+        assert_eq!(info.upload_id, "an-upload-id", "upload ID");
+        assert_eq!(info.key, "a/key", "key");
+        assert!(!info.is_prefix, "is prefix");
+        assert_eq!(
+            info.system.created,
+            UNIX_EPOCH + Duration::from_secs(1_600_000_000),
+            "system created"
+        );
+        assert!(info.custom.is_empty(), "custom");
+    }
+
+    #[test]
+    fn test_part_from_ensured_uplink_c() {
+        {
+            // Valid part.
+            let etag = b"an-etag";
+            let part = Part::from_ensured_uplink_c(&ulksys::UplinkPart {
+                part_number: 3,
+                size: 1024,
+                modified: 1_600_000_000,
+                etag: etag.as_ptr() as *mut _,
+                etag_length: etag.len(),
+            })
+            .expect("valid part");
+
+            assert_eq!(part.part_number, 3, "part number");
+            assert_eq!(part.size, 1024, "size");
+            assert_eq!(
+                part.modified,
+                UNIX_EPOCH + Duration::from_secs(1_600_000_000),
+                "modified"
+            );
+            assert_eq!(part.etag, "an-etag", "etag");
+        }
+
+        {
+            // Part without ETag.
+            let part = Part::from_ensured_uplink_c(&ulksys::UplinkPart {
+                part_number: 2,
+                size: 0,
+                modified: 1_600_000_000,
+                etag: null_mut(),
+                etag_length: 0,
+            })
+            .expect("valid part without ETag");
+
+            assert_eq!(part.etag, "", "etag");
+        }
+
+        {
+            // Modification time before the Unix Epoch time.
+            let etag = b"";
+            if let Error::Internal(error::Internal { ctx_msg, .. }) =
+                Part::from_ensured_uplink_c(&ulksys::UplinkPart {
+                    part_number: 1,
+                    size: 0,
+                    modified: -1,
+                    etag: etag.as_ptr() as *mut _,
+                    etag_length: 0,
+                })
+                .expect_err("modification time before Unix Epoch time")
+            {
+                assert_eq!(
+                    ctx_msg,
+                    "invalid part modification time because it's before the Unix Epoch time or out of range",
+                    "invalid error context message"
+                );
+            } else {
+                panic!("expected an internal error");
+            }
+        }
+    }
+
+    #[test]
+    fn test_list_uploads_options() {
+        {
+            // Pass a valid prefix and cursor.
+            let mut opts = ListUploadsOptions::new("a/b/", "a/b/c")
+                .expect("new shouldn't fail when passing a valid prefix and cursor");
+            assert_eq!(opts.prefix(), "a/b/", "prefix");
+            assert_eq!(opts.cursor(), "a/b/c", "cursor");
+            assert!(!opts.recursive, "recursive");
+            assert!(!opts.system, "system");
+            assert!(!opts.custom, "custom");
+
+            opts.recursive = true;
+            opts.system = true;
+            let c_opts = opts.as_uplink_c();
+            // SAFETY: the pointers are valid as long as opts is.
+            let (c_prefix, c_cursor) =
+                unsafe { (CStr::from_ptr(c_opts.prefix), CStr::from_ptr(c_opts.cursor)) };
+            assert_eq!(c_prefix.to_str().unwrap(), "a/b/", "c-binding prefix");
+            assert_eq!(c_cursor.to_str().unwrap(), "a/b/c", "c-binding cursor");
+            assert!(c_opts.recursive, "c-binding recursive");
+            assert!(c_opts.system, "c-binding system");
+            assert!(!c_opts.custom, "c-binding custom");
+        }
+
+        {
+            // Pass an invalid cursor.
+            if let Error::InvalidArguments(error::Args { names, msg }) =
+                ListUploadsOptions::new("a/b/", "a/b/\0c")
+                    .expect_err("new passing a cursor with NULL bytes")
+            {
+                assert_eq!(names, "cursor", "invalid error argument name");
+                assert_eq!(
+                    msg, "cannot contains null bytes (0 byte). Null byte found at 4",
+                    "invalid error argument message"
+                );
+            } else {
+                panic!("expected an invalid argument error");
+            }
+        }
+    }
+
+    #[test]
+    fn test_iterators_from_uplink_c_null() {
+        assert!(
+            UploadIterator::from_uplink_c(null_mut()).is_err(),
+            "upload iterator"
+        );
+        assert!(
+            PartIterator::from_uplink_c(null_mut()).is_err(),
+            "part iterator"
+        );
+    }
+
+    #[test]
+    fn test_ensurer_ulksys_upload_info_result_valid() {
+        {
+            // Has an info
+            let info_res = ulksys::UplinkUploadInfoResult {
+                info: &mut ulksys::UplinkUploadInfo {
+                    upload_id: CString::new("an-upload-id").unwrap().into_raw(),
+                    key: CString::new("a/key").unwrap().into_raw(),
+                    is_prefix: false,
+                    system: ulksys::UplinkSystemMetadata {
+                        created: 0,
+                        expires: 0,
+                        content_length: 0,
+                    },
+                    custom: ulksys::UplinkCustomMetadata {
+                        entries: null_mut(),
+                        count: 0,
+                    },
+                },
+                error: null_mut::<ulksys::UplinkError>(),
+            };
+
+            info_res.ensure();
+        }
+
+        {
+            // Has an error
+            let info_res = ulksys::UplinkUploadInfoResult {
+                info: null_mut::<ulksys::UplinkUploadInfo>(),
+                error: &mut ulksys::UplinkError {
+                    code: 0,
+                    message: null_mut(),
+                },
+            };
+
+            info_res.ensure();
+        }
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "invalid underlying c-binding returned an invalid UplinkUploadInfoResult; info and error fields are both NULL"
+    )]
+    fn test_ensurer_ulksys_upload_info_result_invalid_both_null() {
+        let info_res = ulksys::UplinkUploadInfoResult {
+            info: null_mut::<ulksys::UplinkUploadInfo>(),
+            error: null_mut::<ulksys::UplinkError>(),
+        };
+
+        info_res.ensure();
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "invalid underlying c-binding returned invalid UplinkUploadInfo; upload_id field is NULL"
+    )]
+    fn test_ensurer_ulksys_upload_info_invalid_upload_id_null() {
+        let info = ulksys::UplinkUploadInfo {
+            upload_id: null_mut(),
+            key: CString::new("a/key").unwrap().into_raw(),
+            is_prefix: false,
+            system: ulksys::UplinkSystemMetadata {
+                created: 0,
+                expires: 0,
+                content_length: 0,
+            },
+            custom: ulksys::UplinkCustomMetadata {
+                entries: null_mut(),
+                count: 0,
+            },
+        };
+
+        info.ensure();
+    }
+
+    #[test]
+    fn test_ensurer_ulksys_part_upload_result_valid() {
+        {
+            // Has a part upload
+            let part_upload_res = ulksys::UplinkPartUploadResult {
+                part_upload: &mut ulksys::UplinkPartUpload { _handle: 0 },
+                error: null_mut::<ulksys::UplinkError>(),
+            };
+
+            part_upload_res.ensure();
+        }
+
+        {
+            // Has an error
+            let part_upload_res = ulksys::UplinkPartUploadResult {
+                part_upload: null_mut::<ulksys::UplinkPartUpload>(),
+                error: &mut ulksys::UplinkError {
+                    code: 0,
+                    message: null_mut(),
+                },
+            };
+
+            part_upload_res.ensure();
+        }
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "invalid underlying c-binding returned an invalid UplinkPartUploadResult; part_upload and error fields are both NOT NULL"
+    )]
+    fn test_ensurer_ulksys_part_upload_result_invalid_both_not_null() {
+        let part_upload_res = ulksys::UplinkPartUploadResult {
+            part_upload: &mut ulksys::UplinkPartUpload { _handle: 0 },
+            error: &mut ulksys::UplinkError {
+                code: 0,
+                message: null_mut(),
+            },
+        };
+
+        part_upload_res.ensure();
+    }
+
+    #[test]
+    fn test_ensurer_ulksys_part_valid() {
+        {
+            // Has an ETag
+            let etag = b"an-etag";
+            let part = ulksys::UplinkPart {
+                part_number: 1,
+                size: 0,
+                modified: 0,
+                etag: etag.as_ptr() as *mut _,
+                etag_length: etag.len(),
+            };
+
+            part.ensure();
+        }
+
+        {
+            // Doesn't have an ETag
+            let part = ulksys::UplinkPart {
+                part_number: 1,
+                size: 0,
+                modified: 0,
+                etag: null_mut(),
+                etag_length: 0,
+            };
+
+            part.ensure();
+        }
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "invalid underlying c-binding returned invalid UplinkPart; etag field is NULL"
+    )]
+    fn test_ensurer_ulksys_part_invalid_etag_null() {
+        let part = ulksys::UplinkPart {
+            part_number: 1,
+            size: 0,
+            modified: 0,
+            etag: null_mut(),
+            etag_length: 7,
+        };
+
+        part.ensure();
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "invalid underlying c-binding returned an invalid UplinkCommitUploadResult; object and error fields are both NULL"
+    )]
+    fn test_ensurer_ulksys_commit_upload_result_invalid_both_null() {
+        let commit_res = ulksys::UplinkCommitUploadResult {
+            object: null_mut::<ulksys::UplinkObject>(),
+            error: null_mut::<ulksys::UplinkError>(),
+        };
+
+        commit_res.ensure();
+    }
+}
@@ -0,0 +1,916 @@
+//! Storj DCS Object and related types.
+
+use crate::{helpers, Ensurer, Error, Result};
+
+use std::collections::BTreeMap;
+use std::ffi::{CStr, CString};
+use std::marker::PhantomData;
+use std::os::raw::c_char;
+use std::time::SystemTime;
+
+use uplink_sys as ulksys;
+
+/// Contains information about an object.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Object {
+    /// The identifier of the object inside of the bucket.
+    pub key: String,
+    /// Indicates if the object is a prefix for other objects.
+    pub is_prefix: bool,
+    /// The system metadata associated with the object.
+    pub system: SystemMetadata,
+    /// The custom metadata associated with the object.
+    pub custom: CustomMetadata,
+}
+
+impl Object {
+    /// Creates an Object instance from the type exposed by the uplink
+    /// c-bindings.
+    ///
+    /// The returned Object has a copy of the values of the passed pointer and
+    /// this function frees it, hence the caller should not use that pointer
+    /// after this call nor free it, even when an error is returned, unless
+    /// it's null.
+    pub(crate) fn from_uplink_c(uc_object: *mut ulksys::UplinkObject) -> Result<Self> {
+        if uc_object.is_null() {
+            return Err(Error::new_invalid_arguments("uc_object", "cannot be null"));
+        }
+
+        let object;
+        // SAFETY: uc_object cannot be null because it's checked at the
+        // beginning of the function and we ensure uc_object doesn't have fields
+        // with NULL pointes through the ensure method of the implemented
+        // Ensurer trait. uc_object isn't used after it's freed.
+        unsafe {
+            object = Self::from_ensured_uplink_c((*uc_object).ensure());
+            ulksys::uplink_free_object(uc_object);
+        }
+
+        object
+    }
+
+    /// Creates an Object instance from an ensured object result returned by
+    /// the uplink c-bindings or an error if the result contains one.
+    ///
+    /// The resources associated with the passed result are freed by this
+    /// function, hence the caller should not use them after this call.
+    pub(crate) fn from_uplink_c_result(object_res: ulksys::UplinkObjectResult) -> Result<Self> {
+        if let Some(err) = Error::new_uplink(object_res.error) {
+            drop_uplink_sys_object_result(object_res);
+            return Err(err);
+        }
+
+        Self::from_uplink_c(object_res.object)
+    }
+
+    /// Creates an Object instance copying the values of an ensured object of
+    /// the uplink c-bindings.
+    fn from_ensured_uplink_c(uc_object: &ulksys::UplinkObject) -> Result<Self> {
+        // SAFETY: the caller ensures that key isn't NULL and the underlying
+        // c-binding returns NULL terminated strings.
+        let key = match unsafe { CStr::from_ptr(uc_object.key) }.to_str() {
+            Ok(k) => String::from(k),
+            Err(err) => {
+                return Err(Error::new_internal_with_inner(
+                    "invalid object key because it contains invalid UTF-8 characters",
+                    err.into(),
+                ));
+            }
+        };
+
+        Ok(Object {
+            key,
+            is_prefix: uc_object.is_prefix,
+            system: SystemMetadata::from_uplink_c(&uc_object.system)?,
+            custom: CustomMetadata::from_uplink_c(&uc_object.custom)?,
+        })
+    }
+}
+
+/// Contains information about an object which is managed by the system and
+/// cannot be modified by the user.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SystemMetadata {
+    /// Time when the object was created. It's the Unix Epoch time for the
+    /// objects which are prefixes.
+    pub created: SystemTime,
+    /// Time when the object expires. None when the object never expires.
+    pub expires: Option<SystemTime>,
+    /// The size of the object content in bytes.
+    pub content_length: u64,
+}
+
+impl SystemMetadata {
+    /// Creates a SystemMetadata instance copying the values of the type
+    /// exposed by the uplink c-bindings.
+    pub(crate) fn from_uplink_c(uc_system: &ulksys::UplinkSystemMetadata) -> Result<Self> {
+        let created = helpers::system_time_from_unix_secs(uc_system.created).ok_or_else(|| {
+            Error::new_internal(
+                "invalid object creation time because it's before the Unix Epoch time or out of range",
+            )
+        })?;
+
+        let expires = if uc_system.expires == 0 {
+            None
+        } else {
+            Some(
+                helpers::system_time_from_unix_secs(uc_system.expires).ok_or_else(|| {
+                    Error::new_internal(
+                        "invalid object expiration time because it's before the Unix Epoch time or out of range",
+                    )
+                })?,
+            )
+        };
+
+        if uc_system.content_length < 0 {
+            return Err(Error::new_internal(
+                "invalid object content length because it's negative",
+            ));
+        }
+
+        Ok(SystemMetadata {
+            created,
+            expires,
+            content_length: uc_system.content_length as u64,
+        })
+    }
+}
+
+/// Contains custom user metadata about an object.
+///
+/// It's a map of key/value pairs ordered by key. Keys and values are UTF-8
+/// strings; the ones inserted through [`CustomMetadata::insert()`] or
+/// converted from a map cannot contain null characters (0 byte), but the ones
+/// of the objects returned by the c-bindings are copied as they are.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct CustomMetadata {
+    entries: BTreeMap<String, String>,
+}
+
+impl CustomMetadata {
+    /// Creates an empty custom metadata.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Inserts an entry. It returns the previous value associated with `key`
+    /// if there was one.
+    ///
+    /// It returns an error if `key` or `value` contains a null character
+    /// (0 byte); the error names the entry as `metadata[<key>]`.
+    pub fn insert(&mut self, key: &str, value: &str) -> Result<Option<String>> {
+        Self::validate_entry(key, value)?;
+        Ok(self.entries.insert(String::from(key), String::from(value)))
+    }
+
+    /// Returns the value associated with `key` if there is one.
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.entries.get(key).map(String::as_str)
+    }
+
+    /// Removes an entry. It returns the value associated with `key` if there
+    /// was one.
+    pub fn remove(&mut self, key: &str) -> Option<String> {
+        self.entries.remove(key)
+    }
+
+    /// Returns an iterator over the entries ordered by key.
+    pub fn iter(&self) -> impl std::iter::Iterator<Item = (&str, &str)> {
+        self.entries.iter().map(|(k, v)| (k.as_str(), v.as_str()))
+    }
+
+    /// Returns the number of entries.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns true if there are no entries.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Creates a CustomMetadata instance copying the values of the type
+    /// exposed by the uplink c-bindings.
+    ///
+    /// The entries are copied verbatim, without validating null characters,
+    /// because they may have been set by other clients.
+    pub(crate) fn from_uplink_c(uc_custom: &ulksys::UplinkCustomMetadata) -> Result<Self> {
+        let mut custom = Self::new();
+        if uc_custom.count == 0 {
+            return Ok(custom);
+        }
+
+        // SAFETY: the underlying c-binding returns an array of count entries
+        // when count isn't 0.
+        let uc_entries = unsafe { std::slice::from_raw_parts(uc_custom.entries, uc_custom.count) };
+        for uc_entry in uc_entries {
+            // SAFETY: the underlying c-binding returns entries whose key and
+            // value point to arrays with the length indicated by their
+            // associated length fields.
+            let (key, value) = unsafe {
+                (
+                    bytes_from_uplink_c(uc_entry.key, uc_entry.key_length),
+                    bytes_from_uplink_c(uc_entry.value, uc_entry.value_length),
+                )
+            };
+
+            let key = std::str::from_utf8(key).map_err(|err| {
+                Error::new_internal_with_inner(
+                    "invalid custom metadata key because it contains invalid UTF-8 characters",
+                    err.into(),
+                )
+            })?;
+            let value = std::str::from_utf8(value).map_err(|err| {
+                Error::new_internal_with_inner(
+                    "invalid custom metadata value because it contains invalid UTF-8 characters",
+                    err.into(),
+                )
+            })?;
+
+            custom
+                .entries
+                .insert(String::from(key), String::from(value));
+        }
+
+        Ok(custom)
+    }
+
+    /// Returns the entries of this CustomMetadata for interoperating with the
+    /// uplink c-bindings.
+    /// The pointer fields of the returned entries point to `self`, so it
+    /// borrows `self` for as long as it lives.
+    pub(crate) fn to_uplink_c(&self) -> UplinkCustomMetadataEntries<'_> {
+        UplinkCustomMetadataEntries(
+            self.entries
+                .iter()
+                .map(|(k, v)| ulksys::UplinkCustomMetadataEntry {
+                    key: k.as_ptr() as *mut c_char,
+                    key_length: k.len(),
+                    value: v.as_ptr() as *mut c_char,
+                    value_length: v.len(),
+                })
+                .collect(),
+            PhantomData,
+        )
+    }
+
+    /// Validates that a key/value pair doesn't contain null characters
+    /// (0 byte). When both are invalid, the error reports the key.
+    fn validate_entry(key: &str, value: &str) -> Result<()> {
+        let name = format!("metadata[{}]", key.escape_debug());
+        helpers::cstring_from_str_fn_arg(&name, key)?;
+        helpers::cstring_from_str_fn_arg(&name, value)?;
+        Ok(())
+    }
+}
+
+impl TryFrom<BTreeMap<String, String>> for CustomMetadata {
+    type Error = Error;
+
+    /// Creates a CustomMetadata from a map of key/value pairs.
+    ///
+    /// It returns an error if any key or value contains a null character
+    /// (0 byte). When there is only one invalid entry, the error is the same
+    /// than the one returned by [`CustomMetadata::insert`], otherwise the
+    /// error names all the invalid entries (e.g. `metadata[owner,sha256]`).
+    fn try_from(entries: BTreeMap<String, String>) -> Result<Self> {
+        let mut invalid = entries
+            .iter()
+            .filter_map(|(k, v)| Self::validate_entry(k, v).err().map(|err| (k, err)));
+
+        let (first_key, first_err) = match invalid.next() {
+            None => return Ok(CustomMetadata { entries }),
+            Some(i) => i,
+        };
+
+        let mut keys = vec![first_key.escape_debug().to_string()];
+        keys.extend(invalid.map(|(k, _)| k.escape_debug().to_string()));
+        if keys.len() == 1 {
+            return Err(first_err);
+        }
+
+        Err(Error::new_invalid_arguments(
+            &format!("metadata[{}]", keys.join(",")),
+            "keys and values cannot contains null bytes (0 byte)",
+        ))
+    }
+}
+
+impl From<CustomMetadata> for BTreeMap<String, String> {
+    fn from(custom: CustomMetadata) -> Self {
+        custom.entries
+    }
+}
+
+/// Holds the entries of a [`CustomMetadata`] in the representation of the
+/// uplink c-bindings.
+/// The entries point to the keys and values of the CustomMetadata which
+/// created them, which is borrowed for the lifetime `'a`.
+pub(crate) struct UplinkCustomMetadataEntries<'a>(
+    Vec<ulksys::UplinkCustomMetadataEntry>,
+    PhantomData<&'a CustomMetadata>,
+);
+
+impl UplinkCustomMetadataEntries<'_> {
+    /// Returns an UplinkCustomMetadata which points to the held entries.
+    /// The pointer fields of the returned struct will be valid as long as
+    /// `self` is.
+    pub(crate) fn as_uplink_c(&self) -> ulksys::UplinkCustomMetadata {
+        ulksys::UplinkCustomMetadata {
+            // The underlying c-bindings don't modify the entries.
+            entries: self.0.as_ptr() as *mut ulksys::UplinkCustomMetadataEntry,
+            count: self.0.len(),
+        }
+    }
+}
+
+/// Iterates over a collection of objects.
+pub struct Iterator {
+    /// The object iterator type of the underlying c-bindings Rust crate that
+    /// an instance of this struct represents and guard its life time until this
+    /// instance drops.
+    inner: *mut ulksys::UplinkObjectIterator,
+    /// The key of the last object returned by the iterator.
+    cursor: Option<String>,
+    /// Indicates that the underlying c-bindings iterator is exhausted, so the
+    /// error, if any, is only returned once.
+    done: bool,
+}
+
+impl Iterator {
+    /// Creates an objects Iterator instance from the type exposed by the uplink
+    /// c-bindings.
+    pub(crate) fn from_uplink_c(uc_iterator: *mut ulksys::UplinkObjectIterator) -> Result<Self> {
+        if uc_iterator.is_null() {
+            return Err(Error::new_invalid_arguments(
+                "uc_iterator",
+                "cannot be null",
+            ));
+        }
+
+        Ok(Iterator {
+            inner: uc_iterator,
+            cursor: None,
+            done: false,
+        })
+    }
+
+    /// Returns the key of the last object returned by this iterator or None if
+    /// it hasn't returned any yet.
+    ///
+    /// The returned value can be used as the cursor of [`ListObjectsOptions`]
+    /// for listing the objects after it.
+    pub fn cursor(&self) -> Option<&str> {
+        self.cursor.as_deref()
+    }
+}
+
+impl std::iter::Iterator for Iterator {
+    type Item = Result<Object>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        // SAFETY: we trust that the underlying c-binding is safe and
+        // self.inner is never NULL because it's checked by the constructor.
+        unsafe {
+            if !ulksys::uplink_object_iterator_next(self.inner) {
+                self.done = true;
+                let uc_error = ulksys::uplink_object_iterator_err(self.inner);
+                return Error::new_uplink(uc_error).map(|err| {
+                    helpers::drop_uplink_sys_error(uc_error);
+                    Err(err)
+                });
+            }
+
+            let object = Object::from_uplink_c(ulksys::uplink_object_iterator_item(self.inner));
+            if let Ok(o) = &object {
+                self.cursor = Some(o.key.clone());
+            }
+
+            Some(object)
+        }
+    }
+}
+
+impl Drop for Iterator {
+    fn drop(&mut self) {
+        // SAFETY: we trust that the underlying c-binding is safe freeing the
+        // memory of a correct UplinkObjectIterator value.
+        unsafe {
+            ulksys::uplink_free_object_iterator(self.inner);
+        }
+    }
+}
+
+/// Options for listing objects.
+#[derive(Debug)]
+pub struct ListObjectsOptions<'a> {
+    prefix: &'a str,
+    c_prefix: CString,
+    cursor: &'a str,
+    c_cursor: CString,
+    /// Lists the objects of all the prefixes under the listed prefix rather
+    /// than collapsing them into prefix objects.
+    pub recursive: bool,
+    /// Includes the system metadata of the listed objects.
+    pub system: bool,
+    /// Includes the custom metadata of the listed objects.
+    pub custom: bool,
+}
+
+impl<'a> ListObjectsOptions<'a> {
+    /// Creates options for listing the objects under `prefix` starting after
+    /// the object with the key `cursor`; the first listed object is the one
+    /// after the cursor.
+    ///
+    /// A non-empty prefix must end with a slash (`/`). Empty values list all
+    /// the objects of the bucket from the beginning.
+    ///
+    /// The options don't list recursively nor include any metadata unless
+    /// the respective fields are set.
+    ///
+    /// It returns an error if prefix or cursor contains a null character
+    /// (0 byte).
+    pub fn new(prefix: &'a str, cursor: &'a str) -> Result<Self> {
+        let c_prefix = helpers::cstring_from_str_fn_arg("prefix", prefix)?;
+        let c_cursor = helpers::cstring_from_str_fn_arg("cursor", cursor)?;
+
+        Ok(ListObjectsOptions {
+            prefix,
+            c_prefix,
+            cursor,
+            c_cursor,
+            recursive: false,
+            system: false,
+            custom: false,
+        })
+    }
+
+    /// Returns the prefix of the listed objects.
+    pub fn prefix(&self) -> &str {
+        self.prefix
+    }
+
+    /// Returns the key of the object after which the listing starts.
+    pub fn cursor(&self) -> &str {
+        self.cursor
+    }
+
+    /// Returns an UplinkListObjectsOptions with the values of this
+    /// ListObjectsOptions for interoperating with the uplink c-bindings.
+    /// The pointer fields of the returned struct will be valid as long as
+    /// `self` is.
+    pub(crate) fn as_uplink_c(&self) -> ulksys::UplinkListObjectsOptions {
+        ulksys::UplinkListObjectsOptions {
+            prefix: self.c_prefix.as_ptr(),
+            cursor: self.c_cursor.as_ptr(),
+            recursive: self.recursive,
+            system: self.system,
+            custom: self.custom,
+        }
+    }
+}
+
+/// Returns the slice of bytes of a c-bindings array pointed by `ptr` with
+/// `len` bytes. It returns an empty slice when `len` is 0, regardless of `ptr`.
+///
+/// # Safety
+///
+/// `ptr` must point to an array of at least `len` bytes when `len` isn't 0 and
+/// the array must not be freed while the returned slice is alive.
+unsafe fn bytes_from_uplink_c<'a>(ptr: *const c_char, len: usize) -> &'a [u8] {
+    if len == 0 {
+        return &[];
+    }
+
+    std::slice::from_raw_parts(ptr as *const u8, len)
+}
+
+impl Ensurer for ulksys::UplinkObjectResult {
+    fn ensure(&self) -> &Self {
+        assert!(!self.object.is_null() || !self.error.is_null(), "invalid underlying c-binding returned an invalid UplinkObjectResult; object and error fields are both NULL");
+        assert!((self.object.is_null() && !self.error.is_null())
+            || (!self.object.is_null() && self.error.is_null()),
+            "invalid underlying c-binding returned an invalid UplinkObjectResult; object and error fields are both NOT NULL");
+        self
+    }
+}
+
+impl Ensurer for ulksys::UplinkObject {
+    fn ensure(&self) -> &Self {
+        assert!(
+            !self.key.is_null(),
+            "invalid underlying c-binding returned invalid UplinkObject; key field is NULL"
+        );
+        self
+    }
+}
+
+/// Calls the associated `free` underlying c-bindings function for releasing
+/// the associated resources of an object result.
+pub(crate) fn drop_uplink_sys_object_result(object_res: ulksys::UplinkObjectResult) {
+    // SAFETY: we trust that the underlying c-binding is safe freeing the
+    // memory of a correct UplinkObjectResult value.
+    unsafe {
+        ulksys::uplink_free_object_result(object_res);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::error;
+
+    use std::ptr::null_mut;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    #[test]
+    fn test_object_from_ensured_uplink_c() {
+        let key = CString::new("a/b/c").unwrap();
+        let (mkey, mvalue) = ("content-type", "text/plain");
+        let mut uc_entries = vec![ulksys::UplinkCustomMetadataEntry {
+            key: mkey.as_ptr() as *mut c_char,
+            key_length: mkey.len(),
+            value: mvalue.as_ptr() as *mut c_char,
+            value_length: mvalue.len(),
+        }];
+
+        let object = Object::from_ensured_uplink_c(&ulksys::UplinkObject {
+            key: key.as_ptr() as *mut c_char,
+            is_prefix: false,
+            system: ulksys::UplinkSystemMetadata {
+                created: 1_600_000_000,
+                expires: 1_700_000_000,
+                content_length: 1024,
+            },
+            custom: ulksys::UplinkCustomMetadata {
+                entries: uc_entries.as_mut_ptr(),
+                count: uc_entries.len(),
+            },
+        })
+        .expect("valid object");
+
+        let mut custom = CustomMetadata::new();
+        custom
+            .insert("content-type", "text/plain")
+            .expect("valid entry");
+        assert_eq!(
+            object,
+            Object {
+                key: String::from("a/b/c"),
+                is_prefix: false,
+                system: SystemMetadata {
+                    created: UNIX_EPOCH + Duration::from_secs(1_600_000_000),
+                    expires: Some(UNIX_EPOCH + Duration::from_secs(1_700_000_000)),
+                    content_length: 1024,
+                },
+                custom,
+            },
+            "object"
+        );
+    }
+
+    #[test]
+    fn test_object_from_ensured_uplink_c_prefix() {
+        let key = CString::new("a/b/").unwrap();
+        let object = Object::from_ensured_uplink_c(&ulksys::UplinkObject {
+            key: key.as_ptr() as *mut c_char,
+            is_prefix: true,
+            system: ulksys::UplinkSystemMetadata {
+                created: 0,
+                expires: 0,
+                content_length: 0,
+            },
+            custom: ulksys::UplinkCustomMetadata {
+                entries: null_mut(),
+                count: 0,
+            },
+        })
+        .expect("valid object");
+
+        assert_eq!(object.key, "a/b/", "key");
+        assert!(object.is_prefix, "is prefix");
+        assert_eq!(object.system.created, UNIX_EPOCH, "created");
+        assert_eq!(object.system.expires, None, "expires");
+        assert_eq!(object.system.content_length, 0, "content length");
+        assert!(object.custom.is_empty(), "custom");
+    }
+
+    #[test]
+    fn test_system_metadata_from_uplink_c_invalid() {
+        let cases = [
+            (
+                ulksys::UplinkSystemMetadata {
+                    created: -1,
+                    expires: 0,
+                    content_length: 0,
+                },
+                "invalid object creation time because it's before the Unix Epoch time or out of range",
+            ),
+            (
+                ulksys::UplinkSystemMetadata {
+                    created: 0,
+                    expires: -1,
+                    content_length: 0,
+                },
+                "invalid object expiration time because it's before the Unix Epoch time or out of range",
+            ),
+            (
+                ulksys::UplinkSystemMetadata {
+                    created: 0,
+                    expires: 0,
+                    content_length: -1,
+                },
+                "invalid object content length because it's negative",
+            ),
+        ];
+
+        for (uc_system, expected_msg) in cases {
+            if let Error::Internal(error::Internal { ctx_msg, .. }) =
+                SystemMetadata::from_uplink_c(&uc_system).expect_err("invalid system metadata")
+            {
+                assert_eq!(ctx_msg, expected_msg, "invalid error context message");
+            } else {
+                panic!("expected an internal error");
+            }
+        }
+    }
+
+    #[test]
+    fn test_custom_metadata() {
+        let mut custom = CustomMetadata::new();
+        assert!(custom.is_empty(), "is empty");
+
+        assert_eq!(custom.insert("b", "2").unwrap(), None, "insert new key");
+        assert_eq!(custom.insert("a", "1").unwrap(), None, "insert new key");
+        assert_eq!(
+            custom.insert("b", "3").unwrap(),
+            Some(String::from("2")),
+            "insert existing key"
+        );
+        assert_eq!(custom.len(), 2, "len");
+        assert_eq!(custom.get("b"), Some("3"), "get existing key");
+        assert_eq!(custom.get("c"), None, "get non-existing key");
+        assert_eq!(
+            custom.iter().collect::<Vec<_>>(),
+            vec![("a", "1"), ("b", "3")],
+            "iter ordered by key"
+        );
+
+        let entries = custom.to_uplink_c();
+        let from_c =
+            CustomMetadata::from_uplink_c(&entries.as_uplink_c()).expect("valid custom metadata");
+        assert_eq!(from_c, custom, "round trip through the c-bindings type");
+
+        assert_eq!(custom.remove("a"), Some(String::from("1")), "remove");
+        assert_eq!(custom.len(), 1, "len after remove");
+
+        let map = BTreeMap::from(custom.clone());
+        assert_eq!(
+            CustomMetadata::try_from(map).expect("valid map"),
+            custom,
+            "round trip through a map"
+        );
+    }
+
+    #[test]
+    fn test_custom_metadata_invalid_entries() {
+        let mut custom = CustomMetadata::new();
+
+        {
+            // Insert an invalid value.
+            if let Error::InvalidArguments(error::Args { names, msg }) = custom
+                .insert("content-type", "text/\0plain")
+                .expect_err("insert a value with NULL bytes")
+            {
+                assert_eq!(
+                    names, "metadata[content-type]",
+                    "invalid error argument name"
+                );
+                assert_eq!(
+                    msg, "cannot contains null bytes (0 byte). Null byte found at 5",
+                    "invalid error argument message"
+                );
+            } else {
+                panic!("expected an invalid argument error");
+            }
+        }
+
+        {
+            // Insert an invalid key.
+            if let Error::InvalidArguments(error::Args { names, msg }) = custom
+                .insert("content\0type", "text/plain")
+                .expect_err("insert a key with NULL bytes")
+            {
+                assert_eq!(
+                    names, "metadata[content\\0type]",
+                    "invalid error argument name"
+                );
+                assert_eq!(
+                    msg, "cannot contains null bytes (0 byte). Null byte found at 7",
+                    "invalid error argument message"
+                );
+            } else {
+                panic!("expected an invalid argument error");
+            }
+        }
+
+        assert!(custom.is_empty(), "invalid entries aren't inserted");
+
+        {
+            // Entries from the c-bindings with NULL bytes, which other
+            // clients may have set.
+            let (key, value) = ("content\0type", "text/\0plain");
+            let mut uc_entries = vec![ulksys::UplinkCustomMetadataEntry {
+                key: key.as_ptr() as *mut c_char,
+                key_length: key.len(),
+                value: value.as_ptr() as *mut c_char,
+                value_length: value.len(),
+            }];
+            let custom = CustomMetadata::from_uplink_c(&ulksys::UplinkCustomMetadata {
+                entries: uc_entries.as_mut_ptr(),
+                count: uc_entries.len(),
+            })
+            .expect("entries with NULL bytes");
+            assert_eq!(custom.get(key), Some(value), "copied entry");
+        }
+
+        {
+            // Map with several invalid entries.
+            let map = BTreeMap::from([
+                (String::from("content-type"), String::from("text/plain")),
+                (String::from("owner"), String::from("\0")),
+                (String::from("sha256"), String::from("ab\0cd")),
+            ]);
+            if let Error::InvalidArguments(error::Args { names, msg }) =
+                CustomMetadata::try_from(map).expect_err("map with NULL bytes")
+            {
+                assert_eq!(
+                    names, "metadata[owner,sha256]",
+                    "invalid error argument name"
+                );
+                assert_eq!(
+                    msg, "keys and values cannot contains null bytes (0 byte)",
+                    "invalid error argument message"
+                );
+            } else {
+                panic!("expected an invalid argument error");
+            }
+        }
+
+        {
+            // Map with one invalid entry.
+            let map = BTreeMap::from([(String::from("owner"), String::from("a\0"))]);
+            if let Error::InvalidArguments(error::Args { names, msg }) =
+                CustomMetadata::try_from(map).expect_err("map with NULL bytes")
+            {
+                assert_eq!(names, "metadata[owner]", "invalid error argument name");
+                assert_eq!(
+                    msg, "cannot contains null bytes (0 byte). Null byte found at 1",
+                    "invalid error argument message"
+                );
+            } else {
+                panic!("expected an invalid argument error");
+            }
+        }
+    }
+
+    #[test]
+    fn test_list_objects_options() {
+        {
+            // Pass a valid prefix and cursor.
+            let mut opts = ListObjectsOptions::new("a/b/", "a/b/c")
+                .expect("new shouldn't fail when passing a valid prefix and cursor");
+            assert_eq!(opts.prefix(), "a/b/", "prefix");
+            assert_eq!(opts.cursor(), "a/b/c", "cursor");
+            assert!(!opts.recursive, "recursive");
+            assert!(!opts.system, "system");
+            assert!(!opts.custom, "custom");
+
+            opts.recursive = true;
+            opts.custom = true;
+            let c_opts = opts.as_uplink_c();
+            // SAFETY: the pointers are valid as long as opts is.
+            let (c_prefix, c_cursor) =
+                unsafe { (CStr::from_ptr(c_opts.prefix), CStr::from_ptr(c_opts.cursor)) };
+            assert_eq!(c_prefix.to_str().unwrap(), "a/b/", "c-binding prefix");
+            assert_eq!(c_cursor.to_str().unwrap(), "a/b/c", "c-binding cursor");
+            assert!(c_opts.recursive, "c-binding recursive");
+            assert!(!c_opts.system, "c-binding system");
+            assert!(c_opts.custom, "c-binding custom");
+        }
+
+        {
+            // Pass an invalid prefix.
+            if let Error::InvalidArguments(error::Args { names, msg }) =
+                ListObjectsOptions::new("a/\0b/", "")
+                    .expect_err("new passing a prefix with NULL bytes")
+            {
+                assert_eq!(names, "prefix", "invalid error argument name");
+                assert_eq!(
+                    msg, "cannot contains null bytes (0 byte). Null byte found at 2",
+                    "invalid error argument message"
+                );
+            } else {
+                panic!("expected an invalid argument error");
+            }
+        }
+
+        {
+            // Pass an invalid cursor.
+            if let Error::InvalidArguments(error::Args { names, msg }) =
+                ListObjectsOptions::new("", "\0").expect_err("new passing a cursor with NULL bytes")
+            {
+                assert_eq!(names, "cursor", "invalid error argument name");
+                assert_eq!(
+                    msg, "cannot contains null bytes (0 byte). Null byte found at 0",
+                    "invalid error argument message"
+                );
+            } else {
+                panic!("expected an invalid argument error");
+            }
+        }
+    }
+
+    #[test]
+    fn test_object_is_send_sync_clone() {
+        fn assert_send_sync_clone<T: Send + Sync + Clone>() {}
+        assert_send_sync_clone::<Object>();
+    }
+
+    #[test]
+    fn test_ensurer_ulksys_object_result_valid() {
+        {
+            // Has an object
+            let object_res = ulksys::UplinkObjectResult {
+                object: &mut ulksys::UplinkObject {
+                    key: CString::new("a-key").unwrap().into_raw(),
+                    is_prefix: false,
+                    system: ulksys::UplinkSystemMetadata {
+                        created: 0,
+                        expires: 0,
+                        content_length: 0,
+                    },
+                    custom: ulksys::UplinkCustomMetadata {
+                        entries: null_mut(),
+                        count: 0,
+                    },
+                },
+                error: null_mut::<ulksys::UplinkError>(),
+            };
+
+            object_res.ensure();
+        }
+
+        {
+            // Has an error
+            let object_res = ulksys::UplinkObjectResult {
+                object: null_mut::<ulksys::UplinkObject>(),
+                error: &mut ulksys::UplinkError {
+                    code: 0,
+                    message: null_mut(),
+                },
+            };
+
+            object_res.ensure();
+        }
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "invalid underlying c-binding returned an invalid UplinkObjectResult; object and error fields are both NULL"
+    )]
+    fn test_ensurer_ulksys_object_result_invalid_both_null() {
+        let object_res = ulksys::UplinkObjectResult {
+            object: null_mut::<ulksys::UplinkObject>(),
+            error: null_mut::<ulksys::UplinkError>(),
+        };
+
+        object_res.ensure();
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "invalid underlying c-binding returned invalid UplinkObject; key field is NULL"
+    )]
+    fn test_ensurer_ulksys_object_invalid_key_null() {
+        let object = ulksys::UplinkObject {
+            key: null_mut(),
+            is_prefix: false,
+            system: ulksys::UplinkSystemMetadata {
+                created: 0,
+                expires: 0,
+                content_length: 0,
+            },
+            custom: ulksys::UplinkCustomMetadata {
+                entries: null_mut(),
+                count: 0,
+            },
+        };
+
+        object.ensure();
+    }
+}
@@ -1,11 +1,945 @@
 //! Storj DCS Project.
 
-/// TODO: document it.
-pub struct Project {}
+use crate::access::{Config, Grant};
+use crate::bucket::{self, Bucket, ListBucketsOptions};
+use crate::download::{Download, DownloadOptions};
+use crate::multipart::{
+    self, CommitUploadOptions, ListUploadsOptions, PartIterator, PartUpload, UploadInfo,
+    UploadIterator,
+};
+use crate::object::{self, CustomMetadata, ListObjectsOptions, Object};
+use crate::upload::{Upload, UploadOptions};
+use crate::{helpers, Ensurer, Error, Result};
+
+use std::os::raw::c_char;
+
+use uplink_sys as ulksys;
+
+/// Provides access to manage buckets and objects.
+#[derive(Debug)]
+pub struct Project {
+    /// The project type of the underlying c-bindings Rust crate that an
+    /// instance of this struct represents and guard its life time until this
+    /// instance drops.
+    /// It's a project result because it's the one that holds the project and
+    /// allows to free its memory.
+    inner: ulksys::UplinkProjectResult,
+}
 
 impl Project {
-    /// TODO: document this method.
-    pub fn revoke_access(&self) {
-        todo!("implement it")
+    /// Opens a project with the specific access grant.
+    pub fn open(grant: &Grant) -> Result<Self> {
+        let projres;
+        // SAFETY: we trust that the underlying c-binding is safe, nonetheless
+        // we ensure projres is correct through the ensure method of the
+        // implemented Ensurer trait.
+        unsafe {
+            projres = *ulksys::uplink_open_project(grant.as_uplink_c()).ensure();
+        }
+
+        Error::new_uplink(projres.error).map_or(Ok(Project { inner: projres }), |err| {
+            drop_uplink_sys_project_result(projres);
+            Err(err)
+        })
+    }
+
+    /// Opens a project with the specific access grant and configuration.
+    pub(crate) fn open_with_config(config: &Config, grant: &Grant) -> Result<Self> {
+        let projres;
+        // SAFETY: we trust that the underlying c-binding is safe, nonetheless
+        // we ensure projres is correct through the ensure method of the
+        // implemented Ensurer trait.
+        unsafe {
+            projres =
+                *ulksys::uplink_config_open_project(config.as_uplink_c(), grant.as_uplink_c())
+                    .ensure();
+        }
+
+        Error::new_uplink(projres.error).map_or(Ok(Project { inner: projres }), |err| {
+            drop_uplink_sys_project_result(projres);
+            Err(err)
+        })
+    }
+
+    /// Closes the project and all the associated resources.
+    ///
+    /// The memory of the underlying c-bindings project is released when this
+    /// instance drops, hence it's still needed to drop it after calling this
+    /// method.
+    pub fn close(&self) -> Result<()> {
+        let uerr;
+        // SAFETY: we trust that the underlying c-binding is safe.
+        unsafe {
+            uerr = ulksys::uplink_close_project(self.inner.project);
+        }
+
+        Error::new_uplink(uerr).map_or(Ok(()), |err| {
+            helpers::drop_uplink_sys_error(uerr);
+            Err(err)
+        })
+    }
+
+    /// Creates a new bucket.
+    ///
+    /// When the bucket already exists it returns an error with the
+    /// `UPLINK_ERROR_BUCKET_ALREADY_EXISTS` code.
+    pub fn create_bucket(&self, bucket: &str) -> Result<Bucket> {
+        let c_bucket = helpers::cstring_from_str_fn_arg("bucket", bucket)?;
+        let bucket_res;
+        // SAFETY: we trust that the underlying c-binding is safe, nonetheless
+        // we ensure bucket_res is correct through the ensure method of the
+        // implemented Ensurer trait.
+        unsafe {
+            bucket_res =
+                *ulksys::uplink_create_bucket(self.inner.project, c_bucket.as_ptr() as *mut c_char)
+                    .ensure();
+        }
+
+        bucket_from_uplink_c_result(bucket_res)
+    }
+
+    /// Ensures that a bucket exists or creates a new one.
+    ///
+    /// When the bucket already exists it returns a valid bucket and no error.
+    pub fn ensure_bucket(&self, bucket: &str) -> Result<Bucket> {
+        let c_bucket = helpers::cstring_from_str_fn_arg("bucket", bucket)?;
+        let bucket_res;
+        // SAFETY: we trust that the underlying c-binding is safe, nonetheless
+        // we ensure bucket_res is correct through the ensure method of the
+        // implemented Ensurer trait.
+        unsafe {
+            bucket_res =
+                *ulksys::uplink_ensure_bucket(self.inner.project, c_bucket.as_ptr() as *mut c_char)
+                    .ensure();
+        }
+
+        bucket_from_uplink_c_result(bucket_res)
+    }
+
+    /// Returns information about a bucket.
+    ///
+    /// When the bucket doesn't exist it returns an error with the
+    /// `UPLINK_ERROR_BUCKET_NOT_FOUND` code.
+    pub fn stat_bucket(&self, bucket: &str) -> Result<Bucket> {
+        let c_bucket = helpers::cstring_from_str_fn_arg("bucket", bucket)?;
+        let bucket_res;
+        // SAFETY: we trust that the underlying c-binding is safe, nonetheless
+        // we ensure bucket_res is correct through the ensure method of the
+        // implemented Ensurer trait.
+        unsafe {
+            bucket_res =
+                *ulksys::uplink_stat_bucket(self.inner.project, c_bucket.as_ptr() as *mut c_char)
+                    .ensure();
+        }
+
+        bucket_from_uplink_c_result(bucket_res)
+    }
+
+    /// Lists the buckets of the project.
+    ///
+    /// When `opts` is None, it lists all the buckets from the beginning.
+    pub fn list_buckets(&self, opts: Option<ListBucketsOptions>) -> Result<bucket::Iterator> {
+        let mut c_opts = opts.as_ref().map(|o| o.as_uplink_c());
+        let c_opts_ptr = c_opts.as_mut().map_or(std::ptr::null_mut(), |o| {
+            o as *mut ulksys::UplinkListBucketsOptions
+        });
+
+        let uc_iterator;
+        // SAFETY: we trust that the underlying c-binding is safe and c_opts
+        // pointer fields are valid while opts isn't dropped.
+        unsafe {
+            uc_iterator = ulksys::uplink_list_buckets(self.inner.project, c_opts_ptr);
+        }
+
+        bucket::Iterator::from_uplink_c(uc_iterator)
+    }
+
+    /// Deletes a bucket.
+    ///
+    /// When the bucket is not empty it returns an error with the
+    /// `UPLINK_ERROR_BUCKET_NOT_EMPTY` code.
+    ///
+    /// It returns None when the satellite doesn't return the information of
+    /// the deleted bucket.
+    pub fn delete_bucket(&self, bucket: &str) -> Result<Option<Bucket>> {
+        let c_bucket = helpers::cstring_from_str_fn_arg("bucket", bucket)?;
+        let bucket_res;
+        // SAFETY: we trust that the underlying c-binding is safe. bucket_res
+        // isn't ensured because both fields are NULL when the satellite
+        // doesn't return the deleted bucket.
+        unsafe {
+            bucket_res =
+                ulksys::uplink_delete_bucket(self.inner.project, c_bucket.as_ptr() as *mut c_char);
+        }
+
+        deleted_bucket_from_uplink_c_result(bucket_res)
+    }
+
+    /// Deletes a bucket and all the objects that it contains.
+    ///
+    /// It returns None when the satellite doesn't return the information of
+    /// the deleted bucket.
+    pub fn delete_bucket_with_objects(&self, bucket: &str) -> Result<Option<Bucket>> {
+        let c_bucket = helpers::cstring_from_str_fn_arg("bucket", bucket)?;
+        let bucket_res;
+        // SAFETY: we trust that the underlying c-binding is safe. bucket_res
+        // isn't ensured because both fields are NULL when the satellite
+        // doesn't return the deleted bucket.
+        unsafe {
+            bucket_res = ulksys::uplink_delete_bucket_with_objects(
+                self.inner.project,
+                c_bucket.as_ptr() as *mut c_char,
+            );
+        }
+
+        deleted_bucket_from_uplink_c_result(bucket_res)
+    }
+
+    /// Lists the objects of a bucket.
+    ///
+    /// When `opts` is None, it lists, not recursively, all the objects of the
+    /// bucket from the beginning and without including any metadata.
+    pub fn list_objects(
+        &self,
+        bucket: &str,
+        opts: Option<ListObjectsOptions>,
+    ) -> Result<object::Iterator> {
+        let c_bucket = helpers::cstring_from_str_fn_arg("bucket", bucket)?;
+        let mut c_opts = opts.as_ref().map(|o| o.as_uplink_c());
+        let c_opts_ptr = c_opts.as_mut().map_or(std::ptr::null_mut(), |o| {
+            o as *mut ulksys::UplinkListObjectsOptions
+        });
+
+        let uc_iterator;
+        // SAFETY: we trust that the underlying c-binding is safe and c_opts
+        // pointer fields are valid while opts isn't dropped.
+        unsafe {
+            uc_iterator = ulksys::uplink_list_objects(
+                self.inner.project,
+                c_bucket.as_ptr() as *mut c_char,
+                c_opts_ptr,
+            );
+        }
+
+        object::Iterator::from_uplink_c(uc_iterator)
+    }
+
+    /// Returns information about an object.
+    ///
+    /// When the object doesn't exist it returns an error with the
+    /// `UPLINK_ERROR_OBJECT_NOT_FOUND` code.
+    pub fn stat_object(&self, bucket: &str, key: &str) -> Result<Object> {
+        let c_bucket = helpers::cstring_from_str_fn_arg("bucket", bucket)?;
+        let c_key = helpers::cstring_from_str_fn_arg("key", key)?;
+        let object_res;
+        // SAFETY: we trust that the underlying c-binding is safe, nonetheless
+        // we ensure object_res is correct through the ensure method of the
+        // implemented Ensurer trait.
+        unsafe {
+            object_res = *ulksys::uplink_stat_object(
+                self.inner.project,
+                c_bucket.as_ptr() as *mut c_char,
+                c_key.as_ptr() as *mut c_char,
+            )
+            .ensure();
+        }
+
+        Object::from_uplink_c_result(object_res)
+    }
+
+    /// Replaces the custom metadata of an object with `metadata`.
+    ///
+    /// The entries which aren't in `metadata` are removed, so an empty
+    /// `metadata` clears the custom metadata of the object.
+    pub fn update_object_metadata(
+        &self,
+        bucket: &str,
+        key: &str,
+        metadata: &CustomMetadata,
+    ) -> Result<()> {
+        let c_bucket = helpers::cstring_from_str_fn_arg("bucket", bucket)?;
+        let c_key = helpers::cstring_from_str_fn_arg("key", key)?;
+        let entries = metadata.to_uplink_c();
+
+        let uerr;
+        // SAFETY: we trust that the underlying c-binding is safe and the
+        // entries pointer fields are valid while metadata isn't dropped.
+        unsafe {
+            uerr = ulksys::uplink_update_object_metadata(
+                self.inner.project,
+                c_bucket.as_ptr() as *mut c_char,
+                c_key.as_ptr() as *mut c_char,
+                entries.as_uplink_c(),
+                std::ptr::null_mut(),
+            );
+        }
+
+        Error::new_uplink(uerr).map_or(Ok(()), |err| {
+            helpers::drop_uplink_sys_error(uerr);
+            Err(err)
+        })
+    }
+
+    /// Deletes an object.
+    ///
+    /// It returns None when the object doesn't exist.
+    pub fn delete_object(&self, bucket: &str, key: &str) -> Result<Option<Object>> {
+        let c_bucket = helpers::cstring_from_str_fn_arg("bucket", bucket)?;
+        let c_key = helpers::cstring_from_str_fn_arg("key", key)?;
+        let object_res;
+        // SAFETY: we trust that the underlying c-binding is safe. object_res
+        // isn't ensured because both fields are NULL when the object doesn't
+        // exist.
+        unsafe {
+            object_res = ulksys::uplink_delete_object(
+                self.inner.project,
+                c_bucket.as_ptr() as *mut c_char,
+                c_key.as_ptr() as *mut c_char,
+            );
+        }
+
+        if let Some(err) = Error::new_uplink(object_res.error) {
+            object::drop_uplink_sys_object_result(object_res);
+            return Err(err);
+        }
+
+        if object_res.object.is_null() {
+            return Ok(None);
+        }
+
+        Object::from_uplink_c(object_res.object).map(Some)
+    }
+
+    /// Copies an object to a new bucket and/or key without downloading and
+    /// uploading its content, returning the information of the copied object.
+    ///
+    /// When the object doesn't exist it returns an error with the
+    /// `UPLINK_ERROR_OBJECT_NOT_FOUND` code and when any of the keys is invalid
+    /// it returns an error with the `UPLINK_ERROR_OBJECT_KEY_INVALID` code.
+    pub fn copy_object(
+        &self,
+        bucket: &str,
+        key: &str,
+        new_bucket: &str,
+        new_key: &str,
+    ) -> Result<Object> {
+        let c_bucket = helpers::cstring_from_str_fn_arg("bucket", bucket)?;
+        let c_key = helpers::cstring_from_str_fn_arg("key", key)?;
+        let c_new_bucket = helpers::cstring_from_str_fn_arg("new_bucket", new_bucket)?;
+        let c_new_key = helpers::cstring_from_str_fn_arg("new_key", new_key)?;
+        let object_res;
+        // SAFETY: we trust that the underlying c-binding is safe, nonetheless
+        // we ensure object_res is correct through the ensure method of the
+        // implemented Ensurer trait.
+        unsafe {
+            object_res = *ulksys::uplink_copy_object(
+                self.inner.project,
+                c_bucket.as_ptr() as *mut c_char,
+                c_key.as_ptr() as *mut c_char,
+                c_new_bucket.as_ptr() as *mut c_char,
+                c_new_key.as_ptr() as *mut c_char,
+                std::ptr::null_mut(),
+            )
+            .ensure();
+        }
+
+        Object::from_uplink_c_result(object_res)
+    }
+
+    /// Moves an object to a new bucket and/or key without downloading and
+    /// uploading its content.
+    ///
+    /// When the object doesn't exist it returns an error with the
+    /// `UPLINK_ERROR_OBJECT_NOT_FOUND` code and when any of the keys is invalid
+    /// it returns an error with the `UPLINK_ERROR_OBJECT_KEY_INVALID` code.
+    pub fn move_object(
+        &self,
+        bucket: &str,
+        key: &str,
+        new_bucket: &str,
+        new_key: &str,
+    ) -> Result<()> {
+        let c_bucket = helpers::cstring_from_str_fn_arg("bucket", bucket)?;
+        let c_key = helpers::cstring_from_str_fn_arg("key", key)?;
+        let c_new_bucket = helpers::cstring_from_str_fn_arg("new_bucket", new_bucket)?;
+        let c_new_key = helpers::cstring_from_str_fn_arg("new_key", new_key)?;
+        let uerr;
+        // SAFETY: we trust that the underlying c-binding is safe.
+        unsafe {
+            uerr = ulksys::uplink_move_object(
+                self.inner.project,
+                c_bucket.as_ptr() as *mut c_char,
+                c_key.as_ptr() as *mut c_char,
+                c_new_bucket.as_ptr() as *mut c_char,
+                c_new_key.as_ptr() as *mut c_char,
+                std::ptr::null_mut(),
+            );
+        }
+
+        Error::new_uplink(uerr).map_or(Ok(()), |err| {
+            helpers::drop_uplink_sys_error(uerr);
+            Err(err)
+        })
+    }
+
+    /// Starts a download of the object stored with the specified key.
+    ///
+    /// When `opts` is None, it downloads the whole object.
+    pub fn download_object(
+        &self,
+        bucket: &str,
+        key: &str,
+        opts: Option<DownloadOptions>,
+    ) -> Result<Download> {
+        let c_bucket = helpers::cstring_from_str_fn_arg("bucket", bucket)?;
+        let c_key = helpers::cstring_from_str_fn_arg("key", key)?;
+        let mut c_opts = opts.as_ref().map(|o| o.to_uplink_c()).transpose()?;
+        let c_opts_ptr = c_opts.as_mut().map_or(std::ptr::null_mut(), |o| {
+            o as *mut ulksys::UplinkDownloadOptions
+        });
+
+        let download_res;
+        // SAFETY: we trust that the underlying c-binding is safe, nonetheless
+        // we ensure download_res is correct through the ensure method of the
+        // implemented Ensurer trait.
+        unsafe {
+            download_res = *ulksys::uplink_download_object(
+                self.inner.project,
+                c_bucket.as_ptr() as *mut c_char,
+                c_key.as_ptr() as *mut c_char,
+                c_opts_ptr,
+            )
+            .ensure();
+        }
+
+        Download::from_uplink_c_result(download_res)
+    }
+
+    /// Starts an upload of an object to the specified key.
+    ///
+    /// The returned [`Upload`] has to be committed for making the object
+    /// available.
+    pub fn upload_object(
+        &self,
+        bucket: &str,
+        key: &str,
+        opts: Option<UploadOptions>,
+    ) -> Result<Upload> {
+        let c_bucket = helpers::cstring_from_str_fn_arg("bucket", bucket)?;
+        let c_key = helpers::cstring_from_str_fn_arg("key", key)?;
+        let mut c_opts = opts.as_ref().map(|o| o.to_uplink_c()).transpose()?;
+        let c_opts_ptr = c_opts.as_mut().map_or(std::ptr::null_mut(), |o| {
+            o as *mut ulksys::UplinkUploadOptions
+        });
+
+        let upload_res;
+        // SAFETY: we trust that the underlying c-binding is safe, nonetheless
+        // we ensure upload_res is correct through the ensure method of the
+        // implemented Ensurer trait.
+        unsafe {
+            upload_res = *ulksys::uplink_upload_object(
+                self.inner.project,
+                c_bucket.as_ptr() as *mut c_char,
+                c_key.as_ptr() as *mut c_char,
+                c_opts_ptr,
+            )
+            .ensure();
+        }
+
+        Upload::from_uplink_c_result(upload_res)
+    }
+
+    /// Starts a multipart upload of an object to the specified key.
+    ///
+    /// The returned [`UploadInfo`] contains the upload ID, which is required
+    /// for uploading the parts and committing or aborting the upload.
+    pub fn begin_upload(
+        &self,
+        bucket: &str,
+        key: &str,
+        opts: Option<UploadOptions>,
+    ) -> Result<UploadInfo> {
+        let c_bucket = helpers::cstring_from_str_fn_arg("bucket", bucket)?;
+        let c_key = helpers::cstring_from_str_fn_arg("key", key)?;
+        let mut c_opts = opts.as_ref().map(|o| o.to_uplink_c()).transpose()?;
+        let c_opts_ptr = c_opts.as_mut().map_or(std::ptr::null_mut(), |o| {
+            o as *mut ulksys::UplinkUploadOptions
+        });
+
+        let info_res;
+        // SAFETY: we trust that the underlying c-binding is safe, nonetheless
+        // we ensure info_res is correct through the ensure method of the
+        // implemented Ensurer trait.
+        unsafe {
+            info_res = *ulksys::uplink_begin_upload(
+                self.inner.project,
+                c_bucket.as_ptr() as *mut c_char,
+                c_key.as_ptr() as *mut c_char,
+                c_opts_ptr,
+            )
+            .ensure();
+        }
+
+        UploadInfo::from_uplink_c_result(info_res)
+    }
+
+    /// Starts the upload of the part number `part_number` of the multipart
+    /// upload `upload_id`.
+    ///
+    /// The returned [`PartUpload`] has to be committed for making the part
+    /// available to the multipart upload. Uploading a part with the same
+    /// number as a previously uploaded one replaces it.
+    pub fn upload_part(
+        &self,
+        bucket: &str,
+        key: &str,
+        upload_id: &str,
+        part_number: u32,
+    ) -> Result<PartUpload> {
+        let c_bucket = helpers::cstring_from_str_fn_arg("bucket", bucket)?;
+        let c_key = helpers::cstring_from_str_fn_arg("key", key)?;
+        let c_upload_id = helpers::cstring_from_str_fn_arg("upload_id", upload_id)?;
+
+        let part_upload_res;
+        // SAFETY: we trust that the underlying c-binding is safe, nonetheless
+        // we ensure part_upload_res is correct through the ensure method of
+        // the implemented Ensurer trait.
+        unsafe {
+            part_upload_res = *ulksys::uplink_upload_part(
+                self.inner.project,
+                c_bucket.as_ptr() as *mut c_char,
+                c_key.as_ptr() as *mut c_char,
+                c_upload_id.as_ptr() as *mut c_char,
+                part_number,
+            )
+            .ensure();
+        }
+
+        PartUpload::from_uplink_c_result(part_upload_res)
+    }
+
+    /// Commits the multipart upload `upload_id` making the object available
+    /// with the content of all its committed parts in ascending part number
+    /// order.
+    pub fn commit_upload(
+        &self,
+        bucket: &str,
+        key: &str,
+        upload_id: &str,
+        opts: Option<CommitUploadOptions>,
+    ) -> Result<Object> {
+        let c_bucket = helpers::cstring_from_str_fn_arg("bucket", bucket)?;
+        let c_key = helpers::cstring_from_str_fn_arg("key", key)?;
+        let c_upload_id = helpers::cstring_from_str_fn_arg("upload_id", upload_id)?;
+        let entries = opts.as_ref().map(|o| o.custom_metadata.to_uplink_c());
+        let mut c_opts = entries.as_ref().map(|e| ulksys::UplinkCommitUploadOptions {
+            custom_metadata: e.as_uplink_c(),
+        });
+        let c_opts_ptr = c_opts.as_mut().map_or(std::ptr::null_mut(), |o| {
+            o as *mut ulksys::UplinkCommitUploadOptions
+        });
+
+        let commit_res;
+        // SAFETY: we trust that the underlying c-binding is safe, nonetheless
+        // we ensure commit_res is correct through the ensure method of the
+        // implemented Ensurer trait. The entries pointer fields are valid
+        // while opts isn't dropped.
+        unsafe {
+            commit_res = *ulksys::uplink_commit_upload(
+                self.inner.project,
+                c_bucket.as_ptr() as *mut c_char,
+                c_key.as_ptr() as *mut c_char,
+                c_upload_id.as_ptr() as *mut c_char,
+                c_opts_ptr,
+            )
+            .ensure();
+        }
+
+        if let Some(err) = Error::new_uplink(commit_res.error) {
+            multipart::drop_uplink_sys_commit_upload_result(commit_res);
+            return Err(err);
+        }
+
+        Object::from_uplink_c(commit_res.object)
+    }
+
+    /// Aborts the multipart upload `upload_id` discarding all its uploaded
+    /// parts.
+    pub fn abort_upload(&self, bucket: &str, key: &str, upload_id: &str) -> Result<()> {
+        let c_bucket = helpers::cstring_from_str_fn_arg("bucket", bucket)?;
+        let c_key = helpers::cstring_from_str_fn_arg("key", key)?;
+        let c_upload_id = helpers::cstring_from_str_fn_arg("upload_id", upload_id)?;
+        let uerr;
+        // SAFETY: we trust that the underlying c-binding is safe.
+        unsafe {
+            uerr = ulksys::uplink_abort_upload(
+                self.inner.project,
+                c_bucket.as_ptr() as *mut c_char,
+                c_key.as_ptr() as *mut c_char,
+                c_upload_id.as_ptr() as *mut c_char,
+            );
+        }
+
+        Error::new_uplink(uerr).map_or(Ok(()), |err| {
+            helpers::drop_uplink_sys_error(uerr);
+            Err(err)
+        })
+    }
+
+    /// Returns an iterator over the pending multipart uploads of the bucket,
+    /// that is, the ones which haven't been committed nor aborted.
+    pub fn list_uploads(
+        &self,
+        bucket: &str,
+        opts: Option<ListUploadsOptions>,
+    ) -> Result<UploadIterator> {
+        let c_bucket = helpers::cstring_from_str_fn_arg("bucket", bucket)?;
+        let mut c_opts = opts.as_ref().map(|o| o.as_uplink_c());
+        let c_opts_ptr = c_opts.as_mut().map_or(std::ptr::null_mut(), |o| {
+            o as *mut ulksys::UplinkListUploadsOptions
+        });
+
+        let uc_iterator;
+        // SAFETY: we trust that the underlying c-binding is safe and c_opts
+        // pointer fields are valid while opts isn't dropped.
+        unsafe {
+            uc_iterator = ulksys::uplink_list_uploads(
+                self.inner.project,
+                c_bucket.as_ptr() as *mut c_char,
+                c_opts_ptr,
+            );
+        }
+
+        UploadIterator::from_uplink_c(uc_iterator)
+    }
+
+    /// Returns an iterator over the committed parts of the multipart upload
+    /// `upload_id` starting after the part number `cursor`; 0 lists them from
+    /// the beginning.
+    pub fn list_upload_parts(
+        &self,
+        bucket: &str,
+        key: &str,
+        upload_id: &str,
+        cursor: u32,
+    ) -> Result<PartIterator> {
+        let c_bucket = helpers::cstring_from_str_fn_arg("bucket", bucket)?;
+        let c_key = helpers::cstring_from_str_fn_arg("key", key)?;
+        let c_upload_id = helpers::cstring_from_str_fn_arg("upload_id", upload_id)?;
+        let mut c_opts = ulksys::UplinkListUploadPartsOptions { cursor };
+
+        let uc_iterator;
+        // SAFETY: we trust that the underlying c-binding is safe.
+        unsafe {
+            uc_iterator = ulksys::uplink_list_upload_parts(
+                self.inner.project,
+                c_bucket.as_ptr() as *mut c_char,
+                c_key.as_ptr() as *mut c_char,
+                c_upload_id.as_ptr() as *mut c_char,
+                &mut c_opts,
+            );
+        }
+
+        PartIterator::from_uplink_c(uc_iterator)
+    }
+
+    /// Revokes the API key embedded in the provided access grant.
+    ///
+    /// When an access grant is revoked, it also revokes any further access
+    /// grants derived from it through [`Grant::share()`].
+    ///
+    /// The access grant used to open this project must be a parent of the
+    /// provided one, otherwise it returns an error.
+    pub fn revoke_access(&self, grant: &Grant) -> Result<()> {
+        let uerr;
+        // SAFETY: we trust that the underlying c-binding is safe.
+        unsafe {
+            uerr = ulksys::uplink_revoke_access(self.inner.project, grant.as_uplink_c());
+        }
+
+        Error::new_uplink(uerr).map_or(Ok(()), |err| {
+            helpers::drop_uplink_sys_error(uerr);
+            Err(err)
+        })
+    }
+}
+
+impl Drop for Project {
+    fn drop(&mut self) {
+        drop_uplink_sys_project_result(self.inner);
+    }
+}
+
+impl Ensurer for ulksys::UplinkProjectResult {
+    fn ensure(&self) -> &Self {
+        assert!(!self.project.is_null() || !self.error.is_null(), "invalid underlying c-binding returned an invalid UplinkProjectResult; project and error fields are both NULL");
+        assert!((self.project.is_null() && !self.error.is_null())
+            || (!self.project.is_null() && self.error.is_null()),
+            "invalid underlying c-binding returned an invalid UplinkProjectResult; project and error fields are both NOT NULL");
+        self
+    }
+}
+
+/// Converts an ensured bucket result returned by the underlying c-bindings
+/// into a Bucket or an error, taking the ownership of its associated
+/// resources.
+fn bucket_from_uplink_c_result(bucket_res: ulksys::UplinkBucketResult) -> Result<Bucket> {
+    if let Some(err) = Error::new_uplink(bucket_res.error) {
+        bucket::drop_uplink_sys_bucket_result(bucket_res);
+        return Err(err);
+    }
+
+    Bucket::from_uplink_c(bucket_res.bucket)
+}
+
+/// Converts a bucket result returned by the underlying c-bindings for a delete
+/// operation into an optional Bucket or an error, taking the ownership of its
+/// associated resources.
+fn deleted_bucket_from_uplink_c_result(
+    bucket_res: ulksys::UplinkBucketResult,
+) -> Result<Option<Bucket>> {
+    if let Some(err) = Error::new_uplink(bucket_res.error) {
+        bucket::drop_uplink_sys_bucket_result(bucket_res);
+        return Err(err);
+    }
+
+    if bucket_res.bucket.is_null() {
+        return Ok(None);
+    }
+
+    Bucket::from_uplink_c(bucket_res.bucket).map(Some)
+}
+
+/// Calls the associated `free` underlying c-bindings function for releasing
+/// the associated resources of a project result.
+fn drop_uplink_sys_project_result(projres: ulksys::UplinkProjectResult) {
+    // SAFETY: we trust that the underlying c-binding is safe freeing the
+    // memory of a correct UplinkProjectResult value.
+    unsafe {
+        ulksys::uplink_free_project_result(projres);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::error;
+    use crate::helpers::test::fake_uplink_c_wrapper;
+
+    use std::mem::ManuallyDrop;
+    use std::ptr::null_mut;
+
+    /// Returns a Project which isn't dropped, so it can be used for testing
+    /// the methods that fail before calling the underlying c-bindings.
+    fn fake_project() -> ManuallyDrop<Project> {
+        fake_uplink_c_wrapper(ulksys::UplinkProject { _handle: 0 }, |project| Project {
+            inner: ulksys::UplinkProjectResult {
+                project,
+                error: null_mut::<ulksys::UplinkError>(),
+            },
+        })
+    }
+
+    /// Asserts that err is an invalid argument error for the argument `name`
+    /// which contains a null byte at the position `pos`.
+    fn assert_null_byte_arg_err(err: Error, name: &str, pos: usize) {
+        if let Error::InvalidArguments(error::Args { names, msg }) = err {
+            assert_eq!(names, name, "invalid error argument name");
+            assert_eq!(
+                msg,
+                format!(
+                    "cannot contains null bytes (0 byte). Null byte found at {}",
+                    pos
+                ),
+                "invalid error argument message"
+            );
+        } else {
+            panic!("expected an invalid argument error");
+        }
+    }
+
+    #[test]
+    fn test_project_bucket_methods_invalid_params() {
+        let project = fake_project();
+
+        assert_null_byte_arg_err(
+            project
+                .create_bucket("a\0bucket")
+                .expect_err("create bucket"),
+            "bucket",
+            1,
+        );
+        assert_null_byte_arg_err(
+            project
+                .ensure_bucket("a\0bucket")
+                .expect_err("ensure bucket"),
+            "bucket",
+            1,
+        );
+        assert_null_byte_arg_err(
+            project.stat_bucket("a\0bucket").expect_err("stat bucket"),
+            "bucket",
+            1,
+        );
+        assert_null_byte_arg_err(
+            project
+                .delete_bucket("a\0bucket")
+                .expect_err("delete bucket"),
+            "bucket",
+            1,
+        );
+        assert_null_byte_arg_err(
+            project
+                .delete_bucket_with_objects("a\0bucket")
+                .expect_err("delete bucket with objects"),
+            "bucket",
+            1,
+        );
+    }
+
+    #[test]
+    fn test_project_object_methods_invalid_params() {
+        let project = fake_project();
+
+        assert_null_byte_arg_err(
+            project
+                .stat_object("a-bucket", "a/\0key")
+                .expect_err("stat object"),
+            "key",
+            2,
+        );
+        assert_null_byte_arg_err(
+            project
+                .delete_object("a\0bucket", "a/key")
+                .expect_err("delete object"),
+            "bucket",
+            1,
+        );
+        assert_null_byte_arg_err(
+            project
+                .update_object_metadata("a-bucket", "a/k\0ey", &CustomMetadata::new())
+                .expect_err("update object metadata"),
+            "key",
+            3,
+        );
+        assert_null_byte_arg_err(
+            project
+                .copy_object("a-bucket", "a/key", "b-bucket", "b/\0key")
+                .expect_err("copy object"),
+            "new_key",
+            2,
+        );
+        assert_null_byte_arg_err(
+            project
+                .move_object("a-bucket", "a/key", "b\0bucket", "b/key")
+                .expect_err("move object"),
+            "new_bucket",
+            1,
+        );
+    }
+
+    #[test]
+    fn test_project_multipart_methods_invalid_params() {
+        let project = fake_project();
+
+        assert_null_byte_arg_err(
+            project
+                .begin_upload("a-bucket", "a/\0key", None)
+                .expect_err("begin upload"),
+            "key",
+            2,
+        );
+        assert_null_byte_arg_err(
+            project
+                .upload_part("a-bucket", "a/key", "an-\0upload-id", 1)
+                .expect_err("upload part"),
+            "upload_id",
+            3,
+        );
+        assert_null_byte_arg_err(
+            project
+                .commit_upload("a\0bucket", "a/key", "an-upload-id", None)
+                .expect_err("commit upload"),
+            "bucket",
+            1,
+        );
+        assert_null_byte_arg_err(
+            project
+                .abort_upload("a-bucket", "a/key", "\0an-upload-id")
+                .expect_err("abort upload"),
+            "upload_id",
+            0,
+        );
+        assert_null_byte_arg_err(
+            project
+                .list_uploads("a-\0bucket", None)
+                .err()
+                .expect("list uploads"),
+            "bucket",
+            2,
+        );
+        assert_null_byte_arg_err(
+            project
+                .list_upload_parts("a-bucket", "\0a/key", "an-upload-id", 0)
+                .err()
+                .expect("list upload parts"),
+            "key",
+            0,
+        );
+    }
+
+    #[test]
+    fn test_ensurer_ulksys_project_result_valid() {
+        {
+            // Has a project
+            let proj_res = ulksys::UplinkProjectResult {
+                project: &mut ulksys::UplinkProject { _handle: 0 },
+                error: null_mut::<ulksys::UplinkError>(),
+            };
+
+            proj_res.ensure();
+        }
+
+        {
+            // Has an error
+            let proj_res = ulksys::UplinkProjectResult {
+                project: null_mut::<ulksys::UplinkProject>(),
+                error: &mut ulksys::UplinkError {
+                    code: 0,
+                    message: null_mut(),
+                },
+            };
+
+            proj_res.ensure();
+        }
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "invalid underlying c-binding returned an invalid UplinkProjectResult; project and error fields are both NULL"
+    )]
+    fn test_ensurer_ulksys_project_result_invalid_both_null() {
+        let proj_res = ulksys::UplinkProjectResult {
+            project: null_mut::<ulksys::UplinkProject>(),
+            error: null_mut::<ulksys::UplinkError>(),
+        };
+
+        proj_res.ensure();
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "invalid underlying c-binding returned an invalid UplinkProjectResult; project and error fields are both NOT NULL"
+    )]
+    fn test_ensurer_ulksys_project_result_invalid_both_not_null() {
+        let proj_res = ulksys::UplinkProjectResult {
+            project: &mut ulksys::UplinkProject { _handle: 0 },
+            error: &mut ulksys::UplinkError {
+                code: 0,
+                message: null_mut(),
+            },
+        };
+
+        proj_res.ensure();
     }
 }
@@ -0,0 +1,154 @@
+//! Helpers for implementing multitenancy in a single app bucket.
+//!
+//! Each tenant owns a prefix of the app bucket whose objects are encrypted
+//! with a key derived from a secret that only the tenant knows. The app
+//! creates the access grant of each tenant from its own access grant with
+//! [`share`], which performs the following steps in the required order:
+//!
+//! 1. Derive the tenant [`EncryptionKey`] from the tenant secret.
+//! 2. Share the app grant restricted to the tenant prefix with
+//!    [`Grant::share`].
+//! 3. Override the encryption key of the tenant prefix in the shared grant
+//!    with [`Grant::override_encryption_key`].
+
+use crate::access::{Grant, Permission, SharePrefix};
+use crate::{helpers, EncryptionKey, Error, Result};
+
+/// Creates an access grant for a tenant from the app access grant.
+///
+/// The returned grant only has the `permission` over the objects under
+/// `prefix` in `bucket` and it encrypts and decrypts them with a key derived
+/// from `secret`. The key is salted with the bucket and the prefix, so
+/// tenants with the same secret don't share the same key.
+///
+/// `prefix` is the tenant prefix. It's normalized to end with a slash (`/`)
+/// because the encryption keys can only be overridden for complete path
+/// segments, so `tenant-a` and `tenant-a/` are the same prefix.
+///
+/// `app_grant` isn't modified; the encryption key is only overridden in the
+/// returned grant.
+///
+/// It returns an error if `prefix` is empty or it starts with a slash, and if
+/// `bucket`, `prefix` or `secret` contains a null character (0 byte).
+pub fn share(
+    app_grant: &Grant,
+    bucket: &str,
+    prefix: &str,
+    secret: &str,
+    permission: &Permission,
+) -> Result<Grant> {
+    if prefix.is_empty() || prefix == "/" {
+        return Err(Error::new_invalid_arguments("prefix", "cannot be empty"));
+    }
+
+    if prefix.starts_with('/') {
+        return Err(Error::new_invalid_arguments(
+            "prefix",
+            "cannot start with a slash (/)",
+        ));
+    }
+
+    let prefix = if prefix.ends_with('/') {
+        String::from(prefix)
+    } else {
+        format!("{}/", prefix)
+    };
+
+    // Validate all the arguments before calling the underlying c-bindings.
+    let share_prefix = SharePrefix::new(bucket, &prefix)?;
+    helpers::cstring_from_str_fn_arg("secret", secret)?;
+
+    let salt = format!("{}/{}", bucket, prefix);
+    let enc_key = EncryptionKey::derive(secret, salt.as_bytes())?;
+
+    let tenant_grant = app_grant.share(permission, vec![share_prefix])?;
+    tenant_grant.override_encryption_key(bucket, &prefix, &enc_key)?;
+    Ok(tenant_grant)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::access::test::{fake_grant, SERIALIZED_ACCESS};
+    use crate::error;
+
+    /// Asserts that err is an invalid argument error for the argument `name`
+    /// with the message `msg`.
+    fn assert_invalid_arg_err(err: Error, name: &str, msg: &str) {
+        if let Error::InvalidArguments(error::Args {
+            names,
+            msg: err_msg,
+        }) = err
+        {
+            assert_eq!(names, name, "invalid error argument name");
+            assert_eq!(err_msg, msg, "invalid error argument message");
+        } else {
+            panic!("expected an invalid argument error");
+        }
+    }
+
+    #[test]
+    fn test_share_invalid_params() {
+        let grant = fake_grant();
+        let perm = Permission::full();
+
+        for prefix in ["", "/"] {
+            assert_invalid_arg_err(
+                share(&grant, "a-bucket", prefix, "secret", &perm).expect_err("empty prefix"),
+                "prefix",
+                "cannot be empty",
+            );
+        }
+
+        assert_invalid_arg_err(
+            share(&grant, "a-bucket", "/tenant", "secret", &perm)
+                .expect_err("prefix starting with a slash"),
+            "prefix",
+            "cannot start with a slash (/)",
+        );
+        assert_invalid_arg_err(
+            share(&grant, "a-\0bucket", "tenant", "secret", &perm)
+                .expect_err("bucket with NULL bytes"),
+            "bucket",
+            "cannot contains null bytes (0 byte). Null byte found at 2",
+        );
+        assert_invalid_arg_err(
+            share(&grant, "a-bucket", "ten\0ant", "secret", &perm)
+                .expect_err("prefix with NULL bytes"),
+            "prefix",
+            "cannot contains null bytes (0 byte). Null byte found at 3",
+        );
+        assert_invalid_arg_err(
+            share(&grant, "a-bucket", "tenant", "sec\0ret", &perm)
+                .expect_err("secret with NULL bytes"),
+            "secret",
+            "cannot contains null bytes (0 byte). Null byte found at 3",
+        );
+    }
+
+    #[test]
+    fn test_share_doesnt_modify_app_grant() {
+        let app_grant = Grant::new(SERIALIZED_ACCESS).expect("valid access grant");
+        let serialized = app_grant.serialize().expect("serialize app grant");
+
+        let tenant_grant = share(
+            &app_grant,
+            "a-bucket",
+            "tenant",
+            "secret",
+            &Permission::full(),
+        )
+        .expect("share");
+
+        assert_eq!(
+            app_grant.serialize().expect("serialize app grant"),
+            serialized,
+            "app grant"
+        );
+        assert_ne!(
+            tenant_grant.serialize().expect("serialize tenant grant"),
+            serialized,
+            "tenant grant"
+        );
+    }
+}
@@ -0,0 +1,317 @@
+//! Storj DCS Upload and related types.
+
+use crate::object::{CustomMetadata, Object};
+use crate::{helpers, Ensurer, Error, Result};
+
+use std::io;
+use std::os::raw::c_void;
+use std::time::SystemTime;
+
+use uplink_sys as ulksys;
+
+/// Uploads an object.
+///
+/// The content of the object is written through the implementation of the
+/// [`std::io::Write`] trait and the upload has to be committed for making the
+/// object available; otherwise it has to be aborted.
+#[derive(Debug)]
+pub struct Upload {
+    /// The upload type of the underlying c-bindings Rust crate that an instance
+    /// of this struct represents and guard its life time until this instance
+    /// drops.
+    /// It's an upload result because it's the one that holds the upload and
+    /// allows to free its memory.
+    inner: ulksys::UplinkUploadResult,
+}
+
+impl Upload {
+    /// Creates an Upload instance from an ensured upload result returned by
+    /// the uplink c-bindings or an error if the result contains one.
+    ///
+    /// The returned Upload owns the passed result and frees its resources when
+    /// it's dropped; when an error is returned they are freed by this function.
+    pub(crate) fn from_uplink_c_result(upload_res: ulksys::UplinkUploadResult) -> Result<Self> {
+        Error::new_uplink(upload_res.error).map_or(Ok(Upload { inner: upload_res }), |err| {
+            drop_uplink_sys_upload_result(upload_res);
+            Err(err)
+        })
+    }
+
+    /// Commits the uploaded data making the object available.
+    ///
+    /// When the upload is already committed or aborted it returns an error
+    /// with the `UPLINK_ERROR_UPLOAD_DONE` code.
+    pub fn commit(&mut self) -> Result<()> {
+        let uerr;
+        // SAFETY: we trust that the underlying c-binding is safe.
+        unsafe {
+            uerr = ulksys::uplink_upload_commit(self.inner.upload);
+        }
+
+        Error::new_uplink(uerr).map_or(Ok(()), |err| {
+            helpers::drop_uplink_sys_error(uerr);
+            Err(err)
+        })
+    }
+
+    /// Aborts the upload discarding the uploaded data.
+    ///
+    /// When the upload is already committed or aborted it returns an error
+    /// with the `UPLINK_ERROR_UPLOAD_DONE` code.
+    pub fn abort(&mut self) -> Result<()> {
+        let uerr;
+        // SAFETY: we trust that the underlying c-binding is safe.
+        unsafe {
+            uerr = ulksys::uplink_upload_abort(self.inner.upload);
+        }
+
+        Error::new_uplink(uerr).map_or(Ok(()), |err| {
+            helpers::drop_uplink_sys_error(uerr);
+            Err(err)
+        })
+    }
+
+    /// Sets the custom metadata to be associated with the object when the
+    /// upload is committed, replacing any previously set one.
+    pub fn set_custom_metadata(&mut self, metadata: &CustomMetadata) -> Result<()> {
+        let entries = metadata.to_uplink_c();
+
+        let uerr;
+        // SAFETY: we trust that the underlying c-binding is safe and the
+        // entries pointer fields are valid while metadata isn't dropped.
+        unsafe {
+            uerr =
+                ulksys::uplink_upload_set_custom_metadata(self.inner.upload, entries.as_uplink_c());
+        }
+
+        Error::new_uplink(uerr).map_or(Ok(()), |err| {
+            helpers::drop_uplink_sys_error(uerr);
+            Err(err)
+        })
+    }
+
+    /// Returns the information about the object being uploaded.
+    pub fn info(&self) -> Result<Object> {
+        let object_res;
+        // SAFETY: we trust that the underlying c-binding is safe, nonetheless
+        // we ensure object_res is correct through the ensure method of the
+        // implemented Ensurer trait.
+        unsafe {
+            object_res = *ulksys::uplink_upload_info(self.inner.upload).ensure();
+        }
+
+        Object::from_uplink_c_result(object_res)
+    }
+}
+
+impl io::Write for Upload {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let write_res;
+        // SAFETY: we trust that the underlying c-binding is safe and it only
+        // reads buf.len() bytes from the pointer.
+        unsafe {
+            write_res = ulksys::uplink_upload_write(
+                self.inner.upload,
+                buf.as_ptr() as *mut c_void,
+                buf.len(),
+            );
+        }
+
+        let res = Error::new_uplink(write_res.error).map_or(Ok(write_res.bytes_written), |err| {
+            Err(io::Error::other(err))
+        });
+        drop_uplink_sys_write_result(write_res);
+        res
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        // The underlying c-binding doesn't buffer any data.
+        Ok(())
+    }
+}
+
+impl Drop for Upload {
+    fn drop(&mut self) {
+        drop_uplink_sys_upload_result(self.inner);
+    }
+}
+
+/// Options for uploading an object.
+#[derive(Debug, Default)]
+pub struct UploadOptions {
+    /// Time when the uploaded object expires. None for never expiring.
+    pub expires: Option<SystemTime>,
+}
+
+impl UploadOptions {
+    /// Returns an UplinkUploadOptions with the values of this UploadOptions
+    /// for interoperating with the uplink c-bindings.
+    ///
+    /// It returns an error if `expires` isn't after the Unix Epoch time, it
+    /// has a fraction of a second or it cannot be represented with the number
+    /// of seconds since the Unix Epoch time, as the time boundaries of a
+    /// [`Permission`](crate::access::Permission).
+    pub(crate) fn to_uplink_c(&self) -> Result<ulksys::UplinkUploadOptions> {
+        let expires = match self.expires {
+            None => 0,
+            Some(t) => {
+                helpers::check_unix_secs_time("opts{expires}", t)?;
+                helpers::unix_secs_from_system_time(t)
+                    .expect("check_unix_secs_time ensures it's representable")
+            }
+        };
+
+        Ok(ulksys::UplinkUploadOptions { expires })
+    }
+}
+
+impl Ensurer for ulksys::UplinkUploadResult {
+    fn ensure(&self) -> &Self {
+        assert!(!self.upload.is_null() || !self.error.is_null(), "invalid underlying c-binding returned an invalid UplinkUploadResult; upload and error fields are both NULL");
+        assert!((self.upload.is_null() && !self.error.is_null())
+            || (!self.upload.is_null() && self.error.is_null()),
+            "invalid underlying c-binding returned an invalid UplinkUploadResult; upload and error fields are both NOT NULL");
+        self
+    }
+}
+
+/// Calls the associated `free` underlying c-bindings function for releasing
+/// the associated resources of an upload result.
+fn drop_uplink_sys_upload_result(upload_res: ulksys::UplinkUploadResult) {
+    // SAFETY: we trust that the underlying c-binding is safe freeing the
+    // memory of a correct UplinkUploadResult value.
+    unsafe {
+        ulksys::uplink_free_upload_result(upload_res);
+    }
+}
+
+/// Calls the associated `free` underlying c-bindings function for releasing
+/// the associated resources of a write result.
+pub(crate) fn drop_uplink_sys_write_result(write_res: ulksys::UplinkWriteResult) {
+    // SAFETY: we trust that the underlying c-binding is safe freeing the
+    // memory of a correct UplinkWriteResult value.
+    unsafe {
+        ulksys::uplink_free_write_result(write_res);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::error;
+
+    use std::ptr::null_mut;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    #[test]
+    fn test_upload_options() {
+        {
+            // Without expiration.
+            let opts = UploadOptions::default();
+            let uc_opts = opts.to_uplink_c().expect("valid options");
+            assert_eq!(uc_opts.expires, 0, "expires");
+        }
+
+        {
+            // With expiration.
+            let opts = UploadOptions {
+                expires: Some(UNIX_EPOCH + Duration::from_secs(1_600_000_000)),
+            };
+            let uc_opts = opts.to_uplink_c().expect("valid options");
+            assert_eq!(uc_opts.expires, 1_600_000_000, "expires");
+        }
+
+        {
+            // With expiration at the Unix Epoch time.
+            let opts = UploadOptions {
+                expires: Some(UNIX_EPOCH),
+            };
+            if let Error::InvalidArguments(error::Args { names, msg }) = opts
+                .to_uplink_c()
+                .expect_err("expiration at the Unix Epoch time")
+            {
+                assert_eq!(names, "opts{expires}", "invalid error argument name");
+                assert_eq!(
+                    msg, "cannot be the Unix Epoch time or before it",
+                    "invalid error argument message"
+                );
+            } else {
+                panic!("expected an invalid argument error");
+            }
+        }
+
+        {
+            // With expiration with a fraction of a second.
+            let opts = UploadOptions {
+                expires: Some(UNIX_EPOCH + Duration::from_millis(1_600_000_000_500)),
+            };
+            if let Error::InvalidArguments(error::Args { names, msg }) = opts
+                .to_uplink_c()
+                .expect_err("expiration with a fraction of a second")
+            {
+                assert_eq!(names, "opts{expires}", "invalid error argument name");
+                assert_eq!(
+                    msg, "cannot have a fraction of a second",
+                    "invalid error argument message"
+                );
+            } else {
+                panic!("expected an invalid argument error");
+            }
+        }
+    }
+
+    #[test]
+    fn test_ensurer_ulksys_upload_result_valid() {
+        {
+            // Has an upload
+            let upload_res = ulksys::UplinkUploadResult {
+                upload: &mut ulksys::UplinkUpload { _handle: 0 },
+                error: null_mut::<ulksys::UplinkError>(),
+            };
+
+            upload_res.ensure();
+        }
+
+        {
+            // Has an error
+            let upload_res = ulksys::UplinkUploadResult {
+                upload: null_mut::<ulksys::UplinkUpload>(),
+                error: &mut ulksys::UplinkError {
+                    code: 0,
+                    message: null_mut(),
+                },
+            };
+
+            upload_res.ensure();
+        }
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "invalid underlying c-binding returned an invalid UplinkUploadResult; upload and error fields are both NULL"
+    )]
+    fn test_ensurer_ulksys_upload_result_invalid_both_null() {
+        let upload_res = ulksys::UplinkUploadResult {
+            upload: null_mut::<ulksys::UplinkUpload>(),
+            error: null_mut::<ulksys::UplinkError>(),
+        };
+
+        upload_res.ensure();
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "invalid underlying c-binding returned an invalid UplinkUploadResult; upload and error fields are both NOT NULL"
+    )]
+    fn test_ensurer_ulksys_upload_result_invalid_both_not_null() {
+        let upload_res = ulksys::UplinkUploadResult {
+            upload: &mut ulksys::UplinkUpload { _handle: 0 },
+            error: &mut ulksys::UplinkError {
+                code: 0,
+                message: null_mut(),
+            },
+        };
+
+        upload_res.ensure();
+    }
+}